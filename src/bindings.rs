@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use serde::Deserialize;
+use serde::de::IntoDeserializer;
+use winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta};
+use winit::keyboard::{Key, KeyCode, NamedKey, PhysicalKey};
+
+use crate::logic::bindings::KeyBindings;
+use crate::logic::input::{InputAction, TextInput};
+
+pub struct Bindings {
+    keys: HashMap<KeyCode, InputAction>,
+    buttons: HashMap<MouseButton, InputAction>,
+}
+
+impl Bindings {
+    pub fn new(key_bindings: &KeyBindings) -> Self {
+        let mut keys = HashMap::new();
+
+        for (action, names) in &key_bindings.keys {
+            for name in names {
+                let key_code: Result<KeyCode, serde::de::value::Error> = KeyCode::deserialize(name.as_str().into_deserializer());
+
+                match key_code {
+                    Ok(key_code) => {
+                        keys.entry(key_code).or_insert(*action);
+                    }
+                    Err(_) => eprintln!("Unknown key {} bound to {:?}", name, action),
+                }
+            }
+        }
+
+        let buttons = HashMap::from([
+            (MouseButton::Left, InputAction::PrimaryClick),
+            (MouseButton::Right, InputAction::SecondaryClick),
+        ]);
+
+        return Self {
+            keys,
+            buttons,
+        };
+    }
+
+    pub fn key_name(key_event: &KeyEvent) -> Option<String> {
+        if key_event.state != ElementState::Pressed || key_event.repeat {
+            return None;
+        }
+
+        return match key_event.physical_key {
+            PhysicalKey::Code(key_code) => Some(format!("{:?}", key_code)),
+            PhysicalKey::Unidentified(_) => None,
+        };
+    }
+
+    pub fn text_input(key_event: &KeyEvent) -> Option<TextInput> {
+        if key_event.state != ElementState::Pressed {
+            return None;
+        }
+
+        return match &key_event.logical_key {
+            Key::Named(NamedKey::Enter) => Some(TextInput::Submit),
+            Key::Named(NamedKey::Escape) => Some(TextInput::Cancel),
+            Key::Named(NamedKey::Backspace) => Some(TextInput::Erase),
+            _ => key_event.text
+                .as_ref()
+                .map(|text| text.chars().filter(|c| !c.is_control()).collect::<String>())
+                .filter(|text| !text.is_empty())
+                .map(TextInput::Insert),
+        };
+    }
+
+    pub fn map_keyboard(&self, key_event: &KeyEvent) -> Option<InputAction> {
+        if key_event.repeat {
+            return None;
+        }
+
+        let action = match key_event.physical_key {
+            PhysicalKey::Code(key_code) => self.keys.get(&key_code).copied(),
+            PhysicalKey::Unidentified(_) => None,
+        };
+
+        if key_event.state == ElementState::Pressed {
+            return action;
+        }
+
+        // Only held actions care about their key being released
+
+        return match action {
+            Some(InputAction::Rewind) => Some(InputAction::RewindRelease),
+            _ => None,
+        };
+    }
+
+    pub fn map_mouse(&self, element_state: ElementState, mouse_button: MouseButton) -> Option<InputAction> {
+        let action = self.buttons.get(&mouse_button).copied();
+
+        if element_state == ElementState::Pressed {
+            return action;
+        }
+
+        return match action {
+            Some(InputAction::PrimaryClick) => Some(InputAction::PrimaryRelease),
+            Some(InputAction::SecondaryClick) => Some(InputAction::SecondaryRelease),
+            _ => None,
+        };
+    }
+
+    pub fn map_wheel(&self, delta: MouseScrollDelta) -> Option<InputAction> {
+        let y = match delta {
+            MouseScrollDelta::LineDelta(_, y) => y,
+            MouseScrollDelta::PixelDelta(position) => position.y as f32,
+        };
+
+        return if y > 0.0 {
+            Some(InputAction::ScrollUp)
+        } else if y < 0.0 {
+            Some(InputAction::ScrollDown)
+        } else {
+            None
+        };
+    }
+}
@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+
+use crate::logic::play::level::{Level, Tile};
+use crate::settings::WindowMode;
+
+pub const USAGE: &str = "Usage: sprint_the_game [OPTIONS]
+
+Options:
+  --level <ID|PATH>  Start directly in a shipped level or a level file
+  --edit <PATH>      Open the level editor on the given file
+  --replay <FILE>    Play back a recorded replay
+  --windowed         Start in a window
+  --fullscreen       Start in fullscreen
+  --size <WxH>       Initial window size in logical pixels, e.g. 1280x720
+  --seed <SEED>      Seed for randomised content
+  -h, --help         Print this help";
+
+// A shipped level is picked by its id, anything else is read as a level file
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelArgument {
+    Id(u32),
+    File(Level),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub level: Option<LevelArgument>,
+    pub edit: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub window_mode: Option<WindowMode>,
+    pub size: Option<(u32, u32)>,
+    pub seed: Option<u64>,
+    pub help: bool,
+}
+
+impl Options {
+    pub fn new() -> Self {
+        return Self {
+            level: None,
+            edit: None,
+            replay: None,
+            window_mode: None,
+            size: None,
+            seed: None,
+            help: false,
+        };
+    }
+
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = Self::new();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| args.next().ok_or_else(|| format!("{} expects a value", name));
+
+            match arg.as_str() {
+                "-h" | "--help" => options.help = true,
+                "--level" => {
+                    let level = value("--level")?;
+
+                    options.level = Some(match level.parse::<u32>() {
+                        Ok(id) if id < Level::builtin_count() => LevelArgument::Id(id),
+                        Ok(_) => return Err(format!("--level expects a level id from 0 to {}, got '{}'", Level::builtin_count() - 1, level)),
+                        Err(_) => {
+                            let file = Level::load(&PathBuf::from(&level))?;
+
+                            if file.find(Tile::Start).is_none() {
+                                return Err(format!("{} has no start tile", level));
+                            }
+
+                            LevelArgument::File(file)
+                        }
+                    });
+                }
+                "--edit" => options.edit = Some(PathBuf::from(value("--edit")?)),
+                "--replay" => options.replay = Some(PathBuf::from(value("--replay")?)),
+                "--windowed" | "--fullscreen" => {
+                    let window_mode = if arg == "--windowed" { WindowMode::Windowed } else { WindowMode::Fullscreen };
+
+                    if options.window_mode.is_some_and(|mode| mode != window_mode) {
+                        return Err("--windowed and --fullscreen cannot be used together".to_string());
+                    }
+
+                    options.window_mode = Some(window_mode);
+                }
+                "--size" => {
+                    let size = value("--size")?;
+
+                    options.size = Some(match size.split_once('x') {
+                        Some((width, height)) => match (width.parse::<u32>(), height.parse::<u32>()) {
+                            (Ok(width), Ok(height)) if width > 0 && height > 0 => (width, height),
+                            _ => return Err(format!("--size expects WIDTHxHEIGHT with positive integers, got '{}'", size)),
+                        },
+                        None => return Err(format!("--size expects WIDTHxHEIGHT, got '{}'", size)),
+                    });
+                }
+                "--seed" => {
+                    let seed = value("--seed")?;
+
+                    options.seed = Some(seed.parse::<u64>().map_err(|_| format!("--seed expects an unsigned integer, got '{}'", seed))?);
+                }
+                _ => return Err(format!("unknown argument '{}'", arg)),
+            }
+        }
+
+        if options.level.is_some() && options.edit.is_some() {
+            return Err("--level and --edit cannot be used together".to_string());
+        }
+
+        return Ok(options);
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
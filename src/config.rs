@@ -0,0 +1,33 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub fn config_dir() -> PathBuf {
+    return dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("sprint_the_game");
+}
+
+pub fn data_dir() -> PathBuf {
+    return dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("sprint_the_game");
+}
+
+
+// Writes next to the target and renames over it, so a crash never leaves a half written file behind
+
+pub fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| format!("Failed to create {}: {}", parent.display(), error))?;
+    }
+
+    let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+    let temporary = path.with_extension(format!("{}.tmp", extension));
+
+    let mut file = fs::File::create(&temporary).map_err(|error| format!("Failed to create {}: {}", temporary.display(), error))?;
+    file.write_all(content.as_bytes()).map_err(|error| format!("Failed to write {}: {}", temporary.display(), error))?;
+    file.sync_all().map_err(|error| format!("Failed to sync {}: {}", temporary.display(), error))?;
+
+    return fs::rename(&temporary, path).map_err(|error| format!("Failed to replace {}: {}", path.display(), error));
+}
\ No newline at end of file
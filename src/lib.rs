@@ -0,0 +1,11 @@
+pub mod sprint_the_game;
+pub mod logic;
+pub mod config;
+pub mod save;
+pub mod settings;
+pub mod cli;
+
+#[cfg(feature = "render")]
+pub mod bindings;
+#[cfg(feature = "render")]
+pub mod renderer;
@@ -1,59 +1,157 @@
-use winit::event::{ElementState, KeyEvent, MouseButton};
+use std::collections::BTreeMap;
+
 use crate::logic::{
+    bindings::BindingsLogic,
     edit::EditLogic,
+    event::{EventBus, GameEvent, Listener},
+    input::{InputAction, KeyCapture, TextInput},
     menu::MenuLogic,
+    pause::PauseLogic,
     play::PlayLogic,
+    scene::{Context, Scene},
+    settings::SettingsLogic,
     victory::VictoryLogic,
 };
-use crate::sprint_the_game::{State, StateTransition};
+use crate::save::SaveGame;
+use crate::sprint_the_game::{Payload, State, StateStack};
 
 pub mod menu;
 pub mod play;
+pub mod pause;
 pub mod victory;
+pub mod panel;
 pub mod edit;
+pub mod bindings;
+pub mod settings;
+pub mod input;
+pub mod scene;
+pub mod event;
 
 pub struct Logic {
-    pub menu: MenuLogic,
-    pub play: PlayLogic,
-    pub victory: VictoryLogic,
-    pub edit: EditLogic,
+    pub context: Context,
+
+    scenes: BTreeMap<State, Box<dyn Scene>>,
 }
 
 impl Logic {
     pub fn new() -> Self {
-        return Self {
-            menu: MenuLogic::new(),
-            play: PlayLogic::new(),
-            victory: VictoryLogic::new(),
-            edit: EditLogic::new(),
+        let save_path = SaveGame::path();
+        let save = SaveGame::load_or_default(&save_path);
+
+        let mut menu = MenuLogic::new();
+        menu.apply_save(&save);
+
+        let mut logic = Self {
+            context: Context {
+                save,
+                save_path,
+                events: EventBus::new(),
+            },
+            scenes: BTreeMap::new(),
         };
+
+        logic.register(State::Menu, menu);
+        logic.register(State::Play, PlayLogic::new());
+        logic.register(State::Pause, PauseLogic::new());
+        logic.register(State::Victory, VictoryLogic::new());
+        logic.register(State::Edit, EditLogic::new());
+        logic.register(State::Bindings, BindingsLogic::new());
+        logic.register(State::Settings, SettingsLogic::new());
+
+        return logic;
+    }
+
+    pub fn register<T: Scene>(&mut self, state: State, scene: T) {
+        self.scenes.insert(state, Box::new(scene));
+    }
+
+    pub fn subscribe<T: Listener + 'static>(&mut self, listener: T) {
+        self.context.events.subscribe(Box::new(listener));
+    }
+
+    pub fn get(&self, state: &State) -> Option<&dyn Scene> {
+        return self.scenes.get(state).map(|scene| scene.as_ref());
+    }
+
+    pub fn get_mut(&mut self, state: &State) -> Option<&mut dyn Scene> {
+        return self.scenes.get_mut(state).map(|scene| scene.as_mut());
+    }
+
+    pub fn scene<T: Scene>(&self, state: &State) -> Option<&T> {
+        return self.scenes.get(state)?.as_any().downcast_ref::<T>();
+    }
+
+    pub fn scene_mut<T: Scene>(&mut self, state: &State) -> Option<&mut T> {
+        return self.scenes.get_mut(state)?.as_any_mut().downcast_mut::<T>();
+    }
+
+    pub fn capture(&self, state: &State) -> KeyCapture {
+        return self.scenes.get(state).map_or(KeyCapture::Actions, |scene| scene.capture());
+    }
+
+    pub fn enter(&mut self, state: &State, payload: Option<Payload>) {
+        if let Some(scene) = self.scenes.get_mut(state) {
+            scene.enter(payload, &mut self.context);
+        }
     }
 
-    pub fn process_keyboard(&mut self, state: &State, key_event: KeyEvent) {
-        match state {
-            State::Menu => self.menu.process_keyboard(key_event),
-            State::Play => self.play.process_keyboard(key_event),
-            State::Victory => self.victory.process_keyboard(key_event),
-            State::Edit => self.edit.process_keyboard(key_event),
+    pub fn process_action(&mut self, state: &State, action: InputAction) {
+        if let Some(scene) = self.scenes.get_mut(state) {
+            scene.process_action(action);
         }
     }
 
-    pub fn process_mouse(&mut self, state: &State, element_state: ElementState, mouse_button: MouseButton) {
-        match state {
-            State::Menu => self.menu.process_mouse(element_state, mouse_button),
-            State::Play => self.play.process_mouse(element_state, mouse_button),
-            State::Victory => self.victory.process_mouse(element_state, mouse_button),
-            State::Edit => self.edit.process_mouse(element_state, mouse_button),
+    pub fn process_key(&mut self, state: &State, key: String) {
+        if let Some(scene) = self.scenes.get_mut(state) {
+            scene.process_key(key);
         }
     }
 
+    pub fn process_text(&mut self, state: &State, input: TextInput) {
+        if let Some(scene) = self.scenes.get_mut(state) {
+            scene.process_text(input);
+        }
+    }
 
-    pub fn update(&mut self, state: &State, transition: &mut StateTransition, delta_time: f32) {
-        match state {
-            State::Menu => self.menu.update(delta_time, transition, &mut self.play, &mut self.edit),
-            State::Play => self.play.update(delta_time, transition, &mut self.victory),
-            State::Victory => self.victory.update(delta_time, transition, &mut self.play),
-            State::Edit => self.edit.update(delta_time, transition, &mut self.menu),
+    pub fn process_cursor(&mut self, state: &State, position: (f32, f32)) {
+        if let Some(scene) = self.scenes.get_mut(state) {
+            scene.process_cursor(position);
         }
     }
+
+    pub fn process_cursor_left(&mut self, state: &State) {
+        if let Some(scene) = self.scenes.get_mut(state) {
+            scene.process_cursor_left();
+        }
+    }
+
+    pub fn layout(&mut self, size: (u32, u32)) {
+        for scene in self.scenes.values_mut() {
+            scene.layout(size);
+        }
+    }
+
+    pub fn update(&mut self, state: &State, stack: &mut StateStack, delta_time: f32) {
+        if let Some(scene) = self.scenes.get_mut(state) {
+            scene.update(delta_time, stack, &mut self.context);
+        }
+    }
+
+    pub fn dispatch_events(&mut self) -> Vec<GameEvent> {
+        let events = self.context.events.drain();
+
+        for event in &events {
+            for scene in self.scenes.values_mut() {
+                scene.handle_event(event, &self.context);
+            }
+        }
+
+        return events;
+    }
+}
+
+impl Default for Logic {
+    fn default() -> Self {
+        return Self::new();
+    }
 }
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{config_dir, write_atomic};
+use crate::logic::event::GameEvent;
+use crate::logic::input::{InputAction, KeyCapture};
+use crate::logic::scene::{Context, Scene};
+use crate::sprint_the_game::StateStack;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindings {
+    pub keys: BTreeMap<InputAction, Vec<String>>,
+}
+
+impl KeyBindings {
+    pub fn new() -> Self {
+        let defaults: [(InputAction, &[&str]); 20] = [
+            (InputAction::MoveUp, &["ArrowUp", "KeyW"]),
+            (InputAction::MoveDown, &["ArrowDown", "KeyS"]),
+            (InputAction::MoveLeft, &["ArrowLeft", "KeyA"]),
+            (InputAction::MoveRight, &["ArrowRight", "KeyD"]),
+            (InputAction::MoveUpLeft, &["KeyQ"]),
+            (InputAction::MoveUpRight, &["KeyE"]),
+            (InputAction::Undo, &["Backspace"]),
+            (InputAction::Rewind, &["KeyZ"]),
+            (InputAction::Redo, &["KeyY"]),
+            (InputAction::Restart, &["KeyR"]),
+            (InputAction::Confirm, &["Enter", "Space"]),
+            (InputAction::Back, &["Escape"]),
+            (InputAction::Bindings, &["F1"]),
+            (InputAction::Settings, &["F2"]),
+            (InputAction::Editor, &["F3"]),
+            (InputAction::Playtest, &["F5"]),
+            (InputAction::Fill, &["KeyF"]),
+            (InputAction::Rename, &["KeyN"]),
+            (InputAction::Save, &["F6"]),
+            (InputAction::Open, &["F7"]),
+        ];
+
+        return Self {
+            keys: defaults
+                .iter()
+                .map(|(action, keys)| (*action, keys.iter().map(|key| key.to_string()).collect()))
+                .collect(),
+        };
+    }
+
+    pub fn path() -> PathBuf {
+        return config_dir().join("bindings.toml");
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+        let mut bindings: Self = toml::from_str(&content).map_err(|error| format!("Failed to parse {}: {}", path.display(), error))?;
+
+        for (action, keys) in Self::new().keys {
+            bindings.keys.entry(action).or_insert(keys);
+        }
+
+        return Ok(bindings);
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = toml::to_string(self).map_err(|error| format!("Failed to serialize key bindings: {}", error))?;
+
+        return write_atomic(path, &content);
+    }
+
+    pub fn action_for(&self, key: &str) -> Option<InputAction> {
+        return self.keys
+            .iter()
+            .find(|(_, keys)| keys.iter().any(|k| k == key))
+            .map(|(action, _)| *action);
+    }
+
+    pub fn conflicts(&self) -> Vec<(String, InputAction, InputAction)> {
+        let mut owners = BTreeMap::<&str, InputAction>::new();
+        let mut conflicts = Vec::new();
+
+        for (action, keys) in &self.keys {
+            for key in keys {
+                match owners.get(key.as_str()) {
+                    Some(owner) if owner != action => conflicts.push((key.clone(), *owner, *action)),
+                    Some(_) => {}
+                    None => {
+                        owners.insert(key, *action);
+                    }
+                }
+            }
+        }
+
+        return conflicts;
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+pub struct BindingsLogic {
+    pub key_bindings: KeyBindings,
+    pub path: PathBuf,
+    pub selected: usize,
+    pub capturing: bool,
+    pub message: Option<String>,
+    pub conflicts: Vec<(String, InputAction, InputAction)>,
+
+    changed: bool,
+    leave: bool,
+}
+
+impl BindingsLogic {
+    pub fn new() -> Self {
+        let path = KeyBindings::path();
+
+        let key_bindings = if path.exists() {
+            KeyBindings::load(&path).unwrap_or_else(|error| {
+                eprintln!("{}, using default key bindings", error);
+
+                KeyBindings::new()
+            })
+        } else {
+            KeyBindings::new()
+        };
+
+        return Self::with_bindings(key_bindings, path);
+    }
+
+    // Edits the given bindings and saves them to the path on leaving, without reading the player's own file
+
+    pub fn with_bindings(key_bindings: KeyBindings, path: PathBuf) -> Self {
+        // A hand edited file can bind one key twice, the first action keeps it until the player fixes it here
+
+        let conflicts = key_bindings.conflicts();
+
+        return Self {
+            key_bindings,
+            path,
+            selected: 0,
+            capturing: false,
+            message: None,
+            conflicts,
+            changed: false,
+            leave: false,
+        };
+    }
+
+    pub fn selected_action(&self) -> InputAction {
+        return *self.key_bindings.keys.keys().nth(self.selected).unwrap();
+    }
+
+    pub fn is_conflicting(&self, action: InputAction) -> bool {
+        return self.conflicts.iter().any(|(_, first, second)| *first == action || *second == action);
+    }
+
+    fn change(&mut self) {
+        self.changed = true;
+        self.conflicts = self.key_bindings.conflicts();
+    }
+
+}
+
+impl Default for BindingsLogic {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Scene for BindingsLogic {
+    fn capture(&self) -> KeyCapture {
+        return if self.capturing { KeyCapture::Key } else { KeyCapture::Actions };
+    }
+
+    fn process_key(&mut self, key: String) {
+        self.capturing = false;
+
+        if key == "Escape" {
+            return;
+        }
+
+        let action = self.selected_action();
+
+        match self.key_bindings.action_for(&key) {
+            Some(owner) if owner == action => {}
+            Some(owner) => self.message = Some(format!("{} is already bound to {:?}", key, owner)),
+            None => {
+                self.key_bindings.keys.get_mut(&action).unwrap().push(key);
+                self.change();
+            }
+        }
+    }
+
+    fn process_action(&mut self, action: InputAction) {
+        if self.capturing {
+            return;
+        }
+
+        self.message = None;
+
+        match action {
+            InputAction::MoveUp => self.selected = self.selected.saturating_sub(1),
+            InputAction::MoveDown => self.selected = (self.selected + 1).min(self.key_bindings.keys.len() - 1),
+            InputAction::Confirm => self.capturing = true,
+            InputAction::Undo => {
+                let action = self.selected_action();
+                let keys = self.key_bindings.keys.get_mut(&action).unwrap();
+
+                if action == InputAction::Back && keys.len() == 1 {
+                    self.message = Some("Back needs at least one key".to_string());
+                } else if keys.pop().is_some() {
+                    self.change();
+                }
+            }
+            InputAction::Restart => {
+                self.key_bindings = KeyBindings::new();
+                self.change();
+            }
+            InputAction::Back => self.leave = true,
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, _delta_time: f32, stack: &mut StateStack, context: &mut Context) {
+        if self.changed {
+            self.changed = false;
+
+            context.events.publish(GameEvent::BindingsChanged(self.key_bindings.clone()));
+        }
+
+        if self.leave {
+            self.leave = false;
+
+            if let Err(error) = self.key_bindings.save(&self.path) {
+                eprintln!("{}", error);
+            }
+
+            stack.pop();
+        }
+    }
+}
@@ -1,17 +1,510 @@
-use winit::event::{ElementState, KeyEvent, MouseButton};
-use crate::logic::menu::MenuLogic;
-use crate::sprint_the_game::StateTransition;
+use std::path::{Path, PathBuf};
+use crate::logic::edit::history::{Change, Command, History};
+use crate::logic::input::{InputAction, KeyCapture, TextInput};
+use crate::logic::play::level::{Level, Tile, MAX_SIZE};
+use crate::logic::scene::{Context, Scene};
+use crate::sprint_the_game::{Payload, State, StateStack};
 
-pub struct EditLogic {}
+pub mod history;
+
+const DEFAULT_SIZE: (u32, u32) = (24, 14);
+const MIN_SIZE: (u32, u32) = (4, 4);
+
+const PALETTE_LEFT: u32 = 40;
+const PALETTE_TOP: u32 = 120;
+pub const PALETTE_ENTRY: (u32, u32) = (180, 40);
+const PALETTE_GAP: u32 = 12;
+
+const GRID_LEFT: u32 = PALETTE_LEFT + PALETTE_ENTRY.0 + 60;
+const GRID_MARGIN: u32 = 80;
+const MAX_CELL: u32 = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditMode {
+    Setup,
+    Paint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pending {
+    Leave,
+    Open(PathBuf),
+}
+
+// Modal boxes drawn over the editor, they take all input until dismissed
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Dialog {
+    Save(String),
+    Open(String),
+    Rename(String),
+    Discard(Pending),
+    Error(String),
+}
+
+// A drag in progress, painted straight onto the level, each cell is recorded the first time the drag changes it
+
+struct Stroke {
+    tile: Tile,
+    changes: Vec<Change>,
+}
+
+pub struct EditLogic {
+    pub path: Option<PathBuf>,
+    pub level: Level,
+    pub mode: EditMode,
+    pub dirty: bool,
+    pub dialog: Option<Dialog>,
+    pub history: History,
+
+    pub new_size: (u32, u32),
+    pub files: Vec<PathBuf>,
+    pub selected: usize,
+
+    pub tile: usize,
+    pub palette: Vec<(u32, u32)>,
+    pub grid: ((u32, u32), u32),
+    pub hovered_cell: Option<(u32, u32)>,
+    pub hovered_tile: Option<usize>,
+
+    size: (u32, u32),
+    stroke: Option<Stroke>,
+    playtest: bool,
+    leave: bool,
+}
 
 impl EditLogic {
     pub fn new() -> Self {
-        return Self {};
+        return Self {
+            path: None,
+            level: Level::new(DEFAULT_SIZE.0, DEFAULT_SIZE.1),
+            mode: EditMode::Setup,
+            dirty: false,
+            dialog: None,
+            history: History::new(),
+            new_size: DEFAULT_SIZE,
+            files: Vec::new(),
+            selected: 0,
+            tile: 1,
+            palette: Vec::new(),
+            grid: ((0, 0), MAX_CELL),
+            hovered_cell: None,
+            hovered_tile: None,
+            size: (1280, 720),
+            stroke: None,
+            playtest: false,
+            leave: false,
+        };
+    }
+
+    pub fn open(&mut self, path: PathBuf) {
+        if path.exists() {
+            match Level::load(&path) {
+                Ok(level) => self.edit(level, Some(path)),
+                Err(error) => {
+                    self.dialog = Some(Dialog::Error(error));
+
+                    if self.mode == EditMode::Setup {
+                        self.show_setup();
+                    }
+                }
+            }
+        } else {
+            self.edit(Level::new(DEFAULT_SIZE.0, DEFAULT_SIZE.1), Some(path));
+        }
     }
 
-    pub fn process_keyboard(&mut self, key_event: KeyEvent) {}
+    pub fn edit(&mut self, level: Level, path: Option<PathBuf>) {
+        self.level = level;
+        self.path = path;
+        self.mode = EditMode::Paint;
+        self.dirty = false;
+        self.stroke = None;
+        self.history.clear();
+        self.hovered_cell = None;
 
-    pub fn process_mouse(&mut self, element_state: ElementState, mouse_button: MouseButton) {}
+        self.layout_grid();
+    }
+
+    fn show_setup(&mut self) {
+        self.mode = EditMode::Setup;
+        self.files = Level::list(&Level::directory());
+        self.selected = self.selected.min(self.setup_rows().len() - 1);
+    }
+
+    pub fn setup_rows(&self) -> Vec<String> {
+        let mut rows = vec![
+            format!("Width: < {} >", self.new_size.0),
+            format!("Height: < {} >", self.new_size.1),
+            "Create new level".to_string(),
+            "Open file...".to_string(),
+        ];
+
+        for file in &self.files {
+            rows.push(format!("Open {}", file.file_name().map_or(String::new(), |name| name.to_string_lossy().to_string())));
+        }
+
+        return rows;
+    }
+
+    // Relative paths are looked up in the levels directory, a missing extension defaults to toml
+
+    fn resolve(text: &str) -> PathBuf {
+        let mut path = PathBuf::from(text.trim());
+
+        if path.is_relative() {
+            path = Level::directory().join(path);
+        }
+
+        if path.extension().is_none() {
+            path.set_extension("toml");
+        }
+
+        return path;
+    }
+
+    fn display(path: &Path) -> String {
+        return path.strip_prefix(Level::directory()).unwrap_or(path).display().to_string();
+    }
+
+    fn prompt_save(&mut self) {
+        let text = self.path.as_deref().map_or("untitled.toml".to_string(), Self::display);
+
+        self.end_stroke();
+        self.dialog = Some(Dialog::Save(text));
+    }
+
+    fn prompt_open(&mut self) {
+        self.end_stroke();
+        self.dialog = Some(Dialog::Open(String::new()));
+    }
+
+    fn prompt_rename(&mut self) {
+        self.end_stroke();
+        self.dialog = Some(Dialog::Rename(self.level.name.clone()));
+    }
+
+    fn save(&mut self, path: PathBuf) {
+        match self.level.save(&path) {
+            Ok(()) => {
+                self.path = Some(path);
+                self.dirty = false;
+            }
+            Err(error) => self.dialog = Some(Dialog::Error(error)),
+        }
+    }
+
+    fn request(&mut self, pending: Pending) {
+        if self.dirty {
+            self.dialog = Some(Dialog::Discard(pending));
+        } else {
+            self.resolve_pending(pending);
+        }
+    }
+
+    fn resolve_pending(&mut self, pending: Pending) {
+        match pending {
+            Pending::Leave => self.leave = true,
+            Pending::Open(path) => self.open(path),
+        }
+    }
+
+    fn process_dialog_action(&mut self, action: InputAction) {
+        match (self.dialog.take(), action) {
+            (Some(Dialog::Discard(pending)), InputAction::Confirm) => {
+                self.dirty = false;
+                self.resolve_pending(pending);
+            }
+            (Some(Dialog::Discard(_)) | Some(Dialog::Error(_)), InputAction::Confirm | InputAction::Back) => {}
+            (dialog, _) => self.dialog = dialog,
+        }
+    }
+
+    fn layout_grid(&mut self) {
+        let (width, height) = self.size;
+
+        let area = (width.saturating_sub(GRID_LEFT + GRID_MARGIN), height.saturating_sub(PALETTE_TOP + GRID_MARGIN));
+        let cell = (area.0 / self.level.width).min(area.1 / self.level.height).clamp(4, MAX_CELL);
+
+        let left = GRID_LEFT + area.0.saturating_sub(cell * self.level.width) / 2;
+        let up = PALETTE_TOP + area.1.saturating_sub(cell * self.level.height) / 2;
+
+        self.grid = ((left, up), cell);
+    }
+
+    fn cell_at(&self, (x, y): (f32, f32)) -> Option<(u32, u32)> {
+        let ((left, up), cell) = self.grid;
+
+        let column = ((x - left as f32) / cell as f32).floor() as i32;
+        let row = ((y - up as f32) / cell as f32).floor() as i32;
+
+        if !self.level.contains((column, row)) {
+            return None;
+        }
+
+        return Some((column as u32, row as u32));
+    }
+
+    // Strokes paint straight onto the level and become a single command once the button is released
+
+    fn begin_stroke(&mut self, tile: Tile) {
+        self.end_stroke();
+
+        self.stroke = Some(Stroke {
+            tile,
+            changes: Vec::new(),
+        });
+
+        self.paint();
+    }
 
-    pub fn update(&mut self, delta_time: f32, transition: &mut StateTransition, menu: &mut MenuLogic) {}
+    fn paint(&mut self) {
+        if let (Some(stroke), Some(cell)) = (&mut self.stroke, self.hovered_cell) {
+            let before = self.level.get(cell);
+
+            // A painted cell already holds the stroke's tile, so it can only change once per stroke
+
+            if self.level.set(cell, stroke.tile) {
+                stroke.changes.push(Change {
+                    cell,
+                    before,
+                    after: stroke.tile,
+                });
+
+                self.dirty = true;
+            }
+        }
+    }
+
+    fn end_stroke(&mut self) {
+        if let Some(stroke) = self.stroke.take() {
+            self.history.record(Command::stroke(stroke.tile, stroke.changes));
+        }
+    }
+
+    fn execute(&mut self, command: Command) {
+        self.end_stroke();
+
+        if self.history.execute(command, &mut self.level) {
+            self.dirty = true;
+            self.layout_grid();
+        }
+    }
+
+    pub fn undo(&mut self) {
+        self.end_stroke();
+
+        if self.history.undo(&mut self.level) {
+            self.dirty = true;
+            self.layout_grid();
+        }
+    }
+
+    pub fn redo(&mut self) {
+        self.end_stroke();
+
+        if self.history.redo(&mut self.level) {
+            self.dirty = true;
+            self.layout_grid();
+        }
+    }
+
+    fn resize(&mut self, (dx, dy): (i32, i32)) {
+        let width = self.level.width.saturating_add_signed(dx).clamp(MIN_SIZE.0, MAX_SIZE.0);
+        let height = self.level.height.saturating_add_signed(dy).clamp(MIN_SIZE.1, MAX_SIZE.1);
+
+        self.execute(Command::resize(&self.level, (width, height)));
+
+        if self.hovered_cell.is_some_and(|(x, y)| x >= width || y >= height) {
+            self.hovered_cell = None;
+        }
+    }
+
+    fn cycle_tile(&mut self, step: i32) {
+        self.tile = (self.tile as i32 + step).rem_euclid(Tile::ALL.len() as i32) as usize;
+    }
+
+    fn process_setup_action(&mut self, action: InputAction) {
+        let rows = self.setup_rows().len();
+
+        match action {
+            InputAction::MoveUp => self.selected = self.selected.saturating_sub(1),
+            InputAction::MoveDown => self.selected = (self.selected + 1).min(rows - 1),
+            InputAction::MoveLeft | InputAction::MoveRight => {
+                let step = if action == InputAction::MoveLeft { -1 } else { 1 };
+
+                match self.selected {
+                    0 => self.new_size.0 = self.new_size.0.saturating_add_signed(step).clamp(MIN_SIZE.0, MAX_SIZE.0),
+                    1 => self.new_size.1 = self.new_size.1.saturating_add_signed(step).clamp(MIN_SIZE.1, MAX_SIZE.1),
+                    _ => {}
+                }
+            }
+            InputAction::Confirm => match self.selected {
+                0 | 1 => {}
+                2 => self.edit(Level::new(self.new_size.0, self.new_size.1), None),
+                3 => self.prompt_open(),
+                i => self.open(self.files[i - 4].clone()),
+            },
+            InputAction::Open => self.prompt_open(),
+            InputAction::Back => self.leave = true,
+            _ => {}
+        }
+    }
+
+    fn process_paint_action(&mut self, action: InputAction) {
+        match action {
+            InputAction::ScrollUp => self.cycle_tile(-1),
+            InputAction::ScrollDown => self.cycle_tile(1),
+            InputAction::MoveLeft => self.resize((-1, 0)),
+            InputAction::MoveRight => self.resize((1, 0)),
+            InputAction::MoveUp => self.resize((0, -1)),
+            InputAction::MoveDown => self.resize((0, 1)),
+            InputAction::PrimaryClick => {
+                if let Some(hovered) = self.hovered_tile {
+                    self.tile = hovered;
+                } else {
+                    self.begin_stroke(Tile::ALL[self.tile]);
+                }
+            }
+            InputAction::SecondaryClick => self.begin_stroke(Tile::Empty),
+            InputAction::PrimaryRelease | InputAction::SecondaryRelease => self.end_stroke(),
+            InputAction::Fill => {
+                if let Some(cell) = self.hovered_cell {
+                    self.execute(Command::fill(&self.level, cell, Tile::ALL[self.tile]));
+                }
+            }
+            InputAction::Undo => self.undo(),
+            InputAction::Redo => self.redo(),
+            InputAction::Rename => self.prompt_rename(),
+            InputAction::Save => self.prompt_save(),
+            InputAction::Open => self.prompt_open(),
+            InputAction::Playtest => {
+                self.end_stroke();
+
+                // Without a start tile the runner has nowhere to stand
+
+                if self.level.find(Tile::Start).is_some() {
+                    self.playtest = true;
+                } else {
+                    self.dialog = Some(Dialog::Error("Place a start tile before playtesting".to_string()));
+                }
+            }
+            InputAction::Back => self.request(Pending::Leave),
+            _ => {}
+        }
+    }
+}
+
+impl Default for EditLogic {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Scene for EditLogic {
+    fn enter(&mut self, payload: Option<Payload>, _context: &mut Context) {
+        match payload {
+            Some(Payload::Path(path)) => self.open(path),
+            _ => self.show_setup(),
+        }
+    }
+
+    fn capture(&self) -> KeyCapture {
+        return match self.dialog {
+            Some(Dialog::Save(_) | Dialog::Open(_) | Dialog::Rename(_)) => KeyCapture::Text,
+            _ => KeyCapture::Actions,
+        };
+    }
+
+    fn process_action(&mut self, action: InputAction) {
+        if self.dialog.is_some() {
+            self.process_dialog_action(action);
+
+            return;
+        }
+
+        match self.mode {
+            EditMode::Setup => self.process_setup_action(action),
+            EditMode::Paint => self.process_paint_action(action),
+        }
+    }
+
+    fn process_text(&mut self, input: TextInput) {
+        let text = match &mut self.dialog {
+            Some(Dialog::Save(text) | Dialog::Open(text) | Dialog::Rename(text)) => text,
+            _ => return,
+        };
+
+        match input {
+            TextInput::Insert(insert) => text.push_str(&insert),
+            TextInput::Erase => {
+                text.pop();
+            }
+            TextInput::Submit => match self.dialog.take() {
+                Some(Dialog::Save(text)) if !text.trim().is_empty() => self.save(Self::resolve(&text)),
+                Some(Dialog::Open(text)) if !text.trim().is_empty() => {
+                    let path = Self::resolve(&text);
+
+                    if path.exists() {
+                        self.request(Pending::Open(path));
+                    } else {
+                        self.dialog = Some(Dialog::Error(format!("{} does not exist", path.display())));
+                    }
+                }
+                Some(Dialog::Rename(text)) => self.execute(Command::rename(&self.level, text.trim())),
+                dialog => self.dialog = dialog,
+            },
+            TextInput::Cancel => self.dialog = None,
+        }
+    }
+
+    fn process_cursor(&mut self, position: (f32, f32)) {
+        if self.dialog.is_some() {
+            self.hovered_cell = None;
+            self.hovered_tile = None;
+
+            return;
+        }
+
+        self.hovered_cell = self.cell_at(position);
+        self.hovered_tile = self.palette.iter().position(|&(left, up)| {
+            position.0 >= left as f32 && position.0 < (left + PALETTE_ENTRY.0) as f32
+                && position.1 >= up as f32 && position.1 < (up + PALETTE_ENTRY.1) as f32
+        });
+
+        self.paint();
+    }
+
+    // A release outside the window never arrives, so the open stroke is committed as soon as the cursor leaves
+
+    fn process_cursor_left(&mut self) {
+        self.end_stroke();
+
+        self.hovered_cell = None;
+        self.hovered_tile = None;
+    }
+
+    fn layout(&mut self, size: (u32, u32)) {
+        self.size = size;
+        self.palette = (0..Tile::ALL.len() as u32)
+            .map(|i| (PALETTE_LEFT, PALETTE_TOP + i * (PALETTE_ENTRY.1 + PALETTE_GAP)))
+            .collect();
+
+        self.layout_grid();
+    }
+
+    fn update(&mut self, _delta_time: f32, stack: &mut StateStack, _context: &mut Context) {
+        if self.playtest {
+            self.playtest = false;
+
+            stack.push_with(State::Play, Payload::Playtest(self.level.clone()));
+        }
+
+        if self.leave {
+            self.leave = false;
+            self.end_stroke();
+
+            stack.replace(State::Menu);
+        }
+    }
 }
\ No newline at end of file
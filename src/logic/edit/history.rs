@@ -0,0 +1,206 @@
+use crate::logic::play::level::{Level, Tile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Change {
+    pub cell: (u32, u32),
+    pub before: Tile,
+    pub after: Tile,
+}
+
+// Every edit to a level is a command that knows how to apply and revert itself
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Paint(Vec<Change>),
+    Erase(Vec<Change>),
+    Fill(Vec<Change>),
+    Resize { before: Level, after: (u32, u32) },
+    Rename { before: String, after: String },
+}
+
+impl Command {
+    pub fn stroke(tile: Tile, changes: Vec<Change>) -> Self {
+        return if tile == Tile::Empty { Command::Erase(changes) } else { Command::Paint(changes) };
+    }
+
+    pub fn paint(level: &Level, cells: &[(u32, u32)], tile: Tile) -> Self {
+        let mut changes: Vec<Change> = Vec::new();
+
+        for &cell in cells {
+            if level.get(cell) != tile && !changes.iter().any(|change| change.cell == cell) {
+                changes.push(Change {
+                    cell,
+                    before: level.get(cell),
+                    after: tile,
+                });
+            }
+        }
+
+        return Self::stroke(tile, changes);
+    }
+
+    // Flood fills the area of equal tiles around the cell, diagonals do not connect on square grids
+
+    pub fn fill(level: &Level, cell: (u32, u32), tile: Tile) -> Self {
+        let target = level.get(cell);
+        let mut changes = Vec::new();
+
+        if target == tile {
+            return Command::Fill(changes);
+        }
+
+        let mut visited = vec![false; (level.width * level.height) as usize];
+        let mut open = vec![cell];
+
+        visited[(cell.1 * level.width + cell.0) as usize] = true;
+
+        while let Some((x, y)) = open.pop() {
+            changes.push(Change {
+                cell: (x, y),
+                before: target,
+                after: tile,
+            });
+
+            for direction in level.directions() {
+                let Some(neighbour) = level.neighbour((x, y), *direction) else {
+                    continue;
+                };
+
+                let index = (neighbour.1 * level.width + neighbour.0) as usize;
+
+                if !visited[index] && level.get(neighbour) == target {
+                    visited[index] = true;
+                    open.push(neighbour);
+                }
+            }
+        }
+
+        return Command::Fill(changes);
+    }
+
+    pub fn resize(level: &Level, size: (u32, u32)) -> Self {
+        return Command::Resize {
+            before: level.clone(),
+            after: size,
+        };
+    }
+
+    pub fn rename(level: &Level, name: &str) -> Self {
+        return Command::Rename {
+            before: level.name.clone(),
+            after: name.to_string(),
+        };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return match self {
+            Command::Paint(changes) | Command::Erase(changes) | Command::Fill(changes) => changes.is_empty(),
+            Command::Resize { before, after } => (before.width, before.height) == *after,
+            Command::Rename { before, after } => before == after,
+        };
+    }
+
+    pub fn apply(&self, level: &mut Level) {
+        match self {
+            Command::Paint(changes) | Command::Erase(changes) | Command::Fill(changes) => {
+                for change in changes {
+                    level.set(change.cell, change.after);
+                }
+            }
+            Command::Resize { after, .. } => level.resize(after.0, after.1),
+            Command::Rename { after, .. } => level.name = after.clone(),
+        }
+    }
+
+    pub fn revert(&self, level: &mut Level) {
+        match self {
+            Command::Paint(changes) | Command::Erase(changes) | Command::Fill(changes) => {
+                for change in changes.iter().rev() {
+                    level.set(change.cell, change.before);
+                }
+            }
+            Command::Resize { before, .. } => *level = before.clone(),
+            Command::Rename { before, .. } => level.name = before.clone(),
+        }
+    }
+}
+
+pub struct History {
+    done: Vec<Command>,
+    undone: Vec<Command>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        return Self {
+            done: Vec::new(),
+            undone: Vec::new(),
+        };
+    }
+
+    pub fn execute(&mut self, command: Command, level: &mut Level) -> bool {
+        if command.is_empty() {
+            return false;
+        }
+
+        command.apply(level);
+
+        self.record(command);
+
+        return true;
+    }
+
+    // Records a command whose effect is already on the level, such as a finished paint stroke
+
+    pub fn record(&mut self, command: Command) {
+        if command.is_empty() {
+            return;
+        }
+
+        self.done.push(command);
+        self.undone.clear();
+    }
+
+    pub fn undo(&mut self, level: &mut Level) -> bool {
+        let Some(command) = self.done.pop() else {
+            return false;
+        };
+
+        command.revert(level);
+
+        self.undone.push(command);
+
+        return true;
+    }
+
+    pub fn redo(&mut self, level: &mut Level) -> bool {
+        let Some(command) = self.undone.pop() else {
+            return false;
+        };
+
+        command.apply(level);
+
+        self.done.push(command);
+
+        return true;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        return !self.done.is_empty();
+    }
+
+    pub fn can_redo(&self) -> bool {
+        return !self.undone.is_empty();
+    }
+
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
\ No newline at end of file
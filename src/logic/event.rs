@@ -0,0 +1,63 @@
+use crate::logic::bindings::KeyBindings;
+use crate::logic::victory::LevelResult;
+use crate::settings::Settings;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    LevelSelected(u32),
+    LevelStarted(u32),
+    LevelWon(LevelResult),
+    RunnerDied(u32),
+    Paused(u32),
+    Resumed(u32),
+    SettingsChanged(Settings),
+    BindingsChanged(KeyBindings),
+}
+
+// Subsystems outside the screens, like audio, achievements or statistics, listen to events without being a scene
+
+pub trait Listener {
+    fn handle_event(&mut self, event: &GameEvent);
+}
+
+// Published during a tick and delivered to every listener when drained, then to every scene and the application in publish order
+
+pub struct EventBus {
+    pending: Vec<GameEvent>,
+    listeners: Vec<Box<dyn Listener>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        return Self {
+            pending: Vec::new(),
+            listeners: Vec::new(),
+        };
+    }
+
+    pub fn publish(&mut self, event: GameEvent) {
+        self.pending.push(event);
+    }
+
+    pub fn subscribe(&mut self, listener: Box<dyn Listener>) {
+        self.listeners.push(listener);
+    }
+
+    pub fn drain(&mut self) -> Vec<GameEvent> {
+        let events = self.pending.drain(..).collect::<Vec<GameEvent>>();
+
+        for event in &events {
+            for listener in &mut self.listeners {
+                listener.handle_event(event);
+            }
+        }
+
+        return events;
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
\ No newline at end of file
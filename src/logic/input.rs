@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    MoveUpLeft,
+    MoveUpRight,
+    Undo,
+    Rewind,
+    RewindRelease,
+    Redo,
+    Restart,
+    Confirm,
+    Back,
+    Bindings,
+    Settings,
+    Editor,
+    Save,
+    Open,
+    Playtest,
+    Fill,
+    Rename,
+    PrimaryClick,
+    SecondaryClick,
+    PrimaryRelease,
+    SecondaryRelease,
+    ScrollUp,
+    ScrollDown,
+}
+
+// What a scene wants from the keyboard instead of bound actions, like a key being rebound or a prompt being typed into
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCapture {
+    Actions,
+    Key,
+    Text,
+}
+
+// Raw text entry for prompts, which bypass the key bindings while they are open
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInput {
+    Insert(String),
+    Erase,
+    Submit,
+    Cancel,
+}
\ No newline at end of file
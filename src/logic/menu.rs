@@ -1,46 +1,276 @@
-use winit::event::{ElementState, KeyEvent, MouseButton};
-use winit::keyboard::{KeyCode, PhysicalKey};
-use crate::logic::edit::EditLogic;
-use crate::logic::play::PlayLogic;
-use crate::sprint_the_game::{State, StateTransition};
+use serde::{Deserialize, Serialize};
+
+use crate::logic::event::GameEvent;
+use crate::logic::input::InputAction;
+use crate::logic::panel::contains;
+use crate::logic::play::level::Level;
+use crate::logic::scene::{Context, Scene};
+use crate::save::SaveGame;
+use crate::sprint_the_game::{Payload, State, StateStack};
+
+// Each level file names the rule that unlocks it, levels without one unlock after the previous level
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnlockRule {
+    Always,
+    #[default]
+    Previous,
+    TotalStars(u32),
+}
 
 pub struct LevelButton {
     pub id: u32,
     pub position: (u32, u32),
+    pub size: (u32, u32),
+    pub unlock: UnlockRule,
+    pub locked: bool,
+}
+
+impl LevelButton {
+    pub fn center(&self) -> (f32, f32) {
+        return (self.position.0 as f32 + self.size.0 as f32 / 2.0, self.position.1 as f32 + self.size.1 as f32 / 2.0);
+    }
+
+    pub fn contains(&self, point: (f32, f32)) -> bool {
+        return contains(self.position, self.size, point);
+    }
 }
 
+const BUTTON_SIZE: (u32, u32) = (250, 100);
+const BUTTON_GAP: u32 = 40;
+
 pub struct MenuLogic {
     pub level_buttons: Vec<LevelButton>,
+    pub hovered: Option<usize>,
+    pub focused: Option<usize>,
+    pub page: usize,
+    pub page_count: usize,
+
+    per_page: usize,
+    cursor: (f32, f32),
+    selected_level: Option<u32>,
+    open_bindings: bool,
+    open_settings: bool,
+    open_editor: bool,
+    quit: bool,
 }
 
 impl MenuLogic {
     pub fn new() -> Self {
+        let level_buttons = (0..Level::builtin_count())
+            .map(|id| {
+                let unlock = Level::builtin(id).map_or(UnlockRule::Previous, |level| level.unlock);
+
+                return LevelButton {
+                    id,
+                    position: (0, 0),
+                    size: BUTTON_SIZE,
+                    unlock,
+                    locked: unlock != UnlockRule::Always,
+                };
+            })
+            .collect::<Vec<LevelButton>>();
+
         return Self {
-            level_buttons: Vec::from([LevelButton {
-                id: 0,
-                position: (25, 25),
-            }, LevelButton {
-                id: 1,
-                position: (300, 300),
-            }])
+            focused: if level_buttons.is_empty() { None } else { Some(0) },
+            per_page: level_buttons.len().max(1),
+            level_buttons,
+            hovered: None,
+            page: 0,
+            page_count: 1,
+            cursor: (-1.0, -1.0),
+            selected_level: None,
+            open_bindings: false,
+            open_settings: false,
+            open_editor: false,
+            quit: false,
         };
     }
 
-    pub fn process_keyboard(&mut self, key_event: KeyEvent) {
-        match key_event {
-            KeyEvent {
-                physical_key,
-                state,
-                ..
-            } => {
-                match physical_key {
-                    _ => {}
+    pub fn layout_buttons(&mut self, (left, up): (u32, u32), (width, height): (u32, u32)) {
+        let columns = ((width + BUTTON_GAP) / (BUTTON_SIZE.0 + BUTTON_GAP)).max(1);
+        let rows = ((height + BUTTON_GAP) / (BUTTON_SIZE.1 + BUTTON_GAP)).max(1);
+
+        self.per_page = (columns * rows) as usize;
+        self.page_count = self.level_buttons.len().div_ceil(self.per_page).max(1);
+
+        let count = self.level_buttons.len();
+
+        for (i, level_button) in self.level_buttons.iter_mut().enumerate() {
+            let slot = i % self.per_page;
+            let on_page = (count - i + slot).min(self.per_page) as u32;
+            let used_columns = on_page.min(columns);
+            let used_rows = on_page.div_ceil(columns);
+
+            let grid_width = used_columns * BUTTON_SIZE.0 + (used_columns - 1) * BUTTON_GAP;
+            let grid_height = used_rows * BUTTON_SIZE.1 + (used_rows - 1) * BUTTON_GAP;
+
+            let (column, row) = (slot as u32 % columns, slot as u32 / columns);
+
+            level_button.position = (
+                left + width.saturating_sub(grid_width) / 2 + column * (BUTTON_SIZE.0 + BUTTON_GAP),
+                up + height.saturating_sub(grid_height) / 2 + row * (BUTTON_SIZE.1 + BUTTON_GAP),
+            );
+        }
+
+        let page = self.focused.map_or(self.page, |focused| focused / self.per_page);
+
+        self.set_page(page);
+    }
+
+    pub fn apply_save(&mut self, save: &SaveGame) {
+        let total_stars = save.total_stars();
+
+        for level_button in &mut self.level_buttons {
+            level_button.locked = match level_button.unlock {
+                UnlockRule::Always => false,
+                UnlockRule::Previous => level_button.id > 0 && !save.is_completed(level_button.id - 1),
+                UnlockRule::TotalStars(stars) => total_stars < stars,
+            };
+        }
+    }
+
+    fn select(&mut self, i: usize) {
+        if !self.level_buttons[i].locked {
+            self.selected_level = Some(self.level_buttons[i].id);
+        }
+    }
+
+    pub fn is_visible(&self, i: usize) -> bool {
+        return i / self.per_page == self.page;
+    }
+
+    fn set_page(&mut self, page: usize) {
+        self.page = page.min(self.page_count - 1);
+
+        if let Some(focused) = self.focused {
+            if !self.is_visible(focused) {
+                self.focused = Some(self.page * self.per_page);
+            }
+        }
+
+        self.process_cursor(self.cursor);
+    }
+
+    fn move_focus(&mut self, direction: (f32, f32)) {
+        let Some(focused) = self.focused else {
+            self.focused = if self.level_buttons.is_empty() { None } else { Some(self.page * self.per_page) };
+
+            return;
+        };
+
+        let origin = self.level_buttons[focused].center();
+
+        let nearest = self.level_buttons
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != focused && self.is_visible(*i))
+            .filter_map(|(i, level_button)| {
+                let center = level_button.center();
+                let (dx, dy) = (center.0 - origin.0, center.1 - origin.1);
+
+                let along = dx * direction.0 + dy * direction.1;
+                let across = (dx * direction.1 - dy * direction.0).abs();
+
+                if along <= 0.0 {
+                    return None;
+                }
+
+                return Some((i, along + 2.0 * across));
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        match nearest {
+            Some((i, _)) => self.focused = Some(i),
+            None if direction.0 > 0.0 && self.page + 1 < self.page_count => {
+                self.focused = Some((self.page + 1) * self.per_page);
+                self.set_page(self.page + 1);
+            }
+            None if direction.0 < 0.0 && self.page > 0 => {
+                self.focused = Some(self.page * self.per_page - 1);
+                self.set_page(self.page - 1);
+            }
+            None => {}
+        }
+    }
+}
+
+impl Default for MenuLogic {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Scene for MenuLogic {
+    fn process_cursor(&mut self, position: (f32, f32)) {
+        self.cursor = position;
+        self.hovered = self.level_buttons
+            .iter()
+            .enumerate()
+            .position(|(i, level_button)| self.is_visible(i) && level_button.contains(position));
+    }
+
+    fn process_action(&mut self, action: InputAction) {
+        match action {
+            InputAction::Bindings => self.open_bindings = true,
+            InputAction::Settings => self.open_settings = true,
+            InputAction::Editor => self.open_editor = true,
+            InputAction::MoveUp => self.move_focus((0.0, -1.0)),
+            InputAction::MoveDown => self.move_focus((0.0, 1.0)),
+            InputAction::MoveLeft => self.move_focus((-1.0, 0.0)),
+            InputAction::MoveRight => self.move_focus((1.0, 0.0)),
+            InputAction::Confirm => {
+                if let Some(focused) = self.focused {
+                    self.select(focused);
+                }
+            }
+            InputAction::Back => self.quit = true,
+            InputAction::ScrollUp => self.set_page(self.page.saturating_sub(1)),
+            InputAction::ScrollDown => self.set_page(self.page + 1),
+            InputAction::PrimaryClick => {
+                if let Some(hovered) = self.hovered {
+                    self.select(hovered);
                 }
             }
+            _ => {}
         }
     }
 
-    pub fn process_mouse(&mut self, element_state: ElementState, mouse_button: MouseButton) {}
+    fn update(&mut self, _delta_time: f32, stack: &mut StateStack, context: &mut Context) {
+        if self.open_bindings {
+            self.open_bindings = false;
+
+            stack.push(State::Bindings);
+        }
+
+        if self.open_settings {
+            self.open_settings = false;
+
+            stack.push(State::Settings);
+        }
 
-    pub fn update(&mut self, delta_time: f32, transition: &mut StateTransition, play: &mut PlayLogic, edit: &mut EditLogic) {}
+        if self.open_editor {
+            self.open_editor = false;
+
+            stack.replace(State::Edit);
+        }
+
+        if let Some(id) = self.selected_level.take() {
+            context.events.publish(GameEvent::LevelSelected(id));
+            stack.replace_with(State::Play, Payload::Level(id));
+        }
+
+        if self.quit {
+            self.quit = false;
+
+            stack.quit();
+        }
+    }
+
+    fn handle_event(&mut self, event: &GameEvent, context: &Context) {
+        if let GameEvent::LevelWon(_) = event {
+            self.apply_save(&context.save);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,128 @@
+use crate::logic::input::InputAction;
+
+pub fn contains((left, up): (u32, u32), (width, height): (u32, u32), (x, y): (f32, f32)) -> bool {
+    return x >= left as f32 && x < (left + width) as f32
+        && y >= up as f32 && y < (up + height) as f32;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arrangement {
+    Row,
+    Column,
+}
+
+pub struct Button<T> {
+    pub choice: T,
+    pub label: &'static str,
+    pub position: (u32, u32),
+    pub size: (u32, u32),
+}
+
+impl<T> Button<T> {
+    pub fn contains(&self, point: (f32, f32)) -> bool {
+        return contains(self.position, self.size, point);
+    }
+}
+
+// A centered panel with its buttons stacked along the bottom edge, shared by the pause and victory overlays
+
+pub struct ButtonPanel<T> {
+    pub position: (u32, u32),
+    pub size: (u32, u32),
+    pub buttons: Vec<Button<T>>,
+    pub focused: usize,
+    pub hovered: Option<usize>,
+
+    arrangement: Arrangement,
+    button_size: (u32, u32),
+    gap: u32,
+    bounds: (u32, u32),
+}
+
+impl<T: Copy> ButtonPanel<T> {
+    pub fn new(arrangement: Arrangement, size: (u32, u32), button_size: (u32, u32), gap: u32) -> Self {
+        return Self {
+            position: (0, 0),
+            size,
+            buttons: Vec::new(),
+            focused: 0,
+            hovered: None,
+            arrangement,
+            button_size,
+            gap,
+            bounds: (0, 0),
+        };
+    }
+
+    pub fn set_buttons(&mut self, buttons: &[(T, &'static str)]) {
+        self.buttons = buttons
+            .iter()
+            .map(|(choice, label)| Button {
+                choice: *choice,
+                label,
+                position: (0, 0),
+                size: self.button_size,
+            })
+            .collect();
+
+        self.reset();
+        self.layout(self.bounds);
+    }
+
+    pub fn reset(&mut self) {
+        self.focused = 0;
+        self.hovered = None;
+    }
+
+    pub fn layout(&mut self, (width, height): (u32, u32)) {
+        self.bounds = (width, height);
+
+        let left = (width.saturating_sub(self.size.0) / 2).max(self.gap);
+        let up = (height.saturating_sub(self.size.1) / 2).max(self.gap);
+
+        self.position = (left, up);
+
+        let (button_width, button_height) = self.button_size;
+        let count = self.buttons.len() as u32;
+
+        for (i, button) in self.buttons.iter_mut().enumerate() {
+            let i = i as u32;
+
+            button.position = match self.arrangement {
+                Arrangement::Row => (
+                    left + (self.size.0 + self.gap).saturating_sub(count * (button_width + self.gap)) / 2 + i * (button_width + self.gap),
+                    up + self.size.1 - self.gap - button_height,
+                ),
+                Arrangement::Column => (
+                    left + (self.size.0 - button_width) / 2,
+                    up + self.size.1 - (count - i) * (button_height + self.gap),
+                ),
+            };
+        }
+    }
+
+    pub fn process_cursor(&mut self, position: (f32, f32)) {
+        self.hovered = self.buttons.iter().position(|button| button.contains(position));
+    }
+
+    // Moves the focus along the arrangement, returns the choice once a button is confirmed or clicked
+
+    pub fn process_action(&mut self, action: InputAction) -> Option<T> {
+        let (previous, next) = match self.arrangement {
+            Arrangement::Row => (InputAction::MoveLeft, InputAction::MoveRight),
+            Arrangement::Column => (InputAction::MoveUp, InputAction::MoveDown),
+        };
+
+        if action == previous {
+            self.focused = self.focused.saturating_sub(1);
+        } else if action == next {
+            self.focused = (self.focused + 1).min(self.buttons.len().saturating_sub(1));
+        } else if action == InputAction::Confirm {
+            return self.buttons.get(self.focused).map(|button| button.choice);
+        } else if action == InputAction::PrimaryClick {
+            return self.hovered.map(|hovered| self.buttons[hovered].choice);
+        }
+
+        return None;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,99 @@
+use crate::logic::event::GameEvent;
+use crate::logic::input::InputAction;
+use crate::logic::panel::{Arrangement, ButtonPanel};
+use crate::logic::scene::{Context, Scene};
+use crate::sprint_the_game::{Payload, State, StateStack};
+
+const BUTTON_SIZE: (u32, u32) = (360, 60);
+const BUTTON_GAP: u32 = 30;
+const PANEL_SIZE: (u32, u32) = (360 + 2 * 60, 100 + 4 * 60 + 5 * 30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseChoice {
+    Resume,
+    Restart,
+    Settings,
+    Menu,
+}
+
+pub struct PauseLogic {
+    pub panel: ButtonPanel<PauseChoice>,
+
+    level_id: u32,
+    chosen: Option<PauseChoice>,
+}
+
+impl PauseLogic {
+    pub fn new() -> Self {
+        let mut panel = ButtonPanel::new(Arrangement::Column, PANEL_SIZE, BUTTON_SIZE, BUTTON_GAP);
+
+        panel.set_buttons(&[
+            (PauseChoice::Resume, "Resume"),
+            (PauseChoice::Restart, "Restart"),
+            (PauseChoice::Settings, "Settings"),
+            (PauseChoice::Menu, "Quit to Menu"),
+        ]);
+
+        return Self {
+            panel,
+            level_id: 0,
+            chosen: None,
+        };
+    }
+
+    pub fn open(&mut self) {
+        self.panel.reset();
+        self.chosen = None;
+    }
+}
+
+impl Default for PauseLogic {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Scene for PauseLogic {
+    fn enter(&mut self, payload: Option<Payload>, _context: &mut Context) {
+        if let Some(Payload::Level(id)) = payload {
+            self.level_id = id;
+        }
+
+        self.open();
+    }
+
+    fn layout(&mut self, size: (u32, u32)) {
+        self.panel.layout(size);
+    }
+
+    fn process_cursor(&mut self, position: (f32, f32)) {
+        self.panel.process_cursor(position);
+    }
+
+    fn process_action(&mut self, action: InputAction) {
+        if action == InputAction::Back {
+            self.chosen = Some(PauseChoice::Resume);
+        } else if let Some(choice) = self.panel.process_action(action) {
+            self.chosen = Some(choice);
+        }
+    }
+
+    fn update(&mut self, _delta_time: f32, stack: &mut StateStack, context: &mut Context) {
+        match self.chosen.take() {
+            Some(PauseChoice::Resume) => {
+                context.events.publish(GameEvent::Resumed(self.level_id));
+                stack.pop();
+            }
+            Some(PauseChoice::Restart) => {
+                stack.pop();
+                stack.replace_with(State::Play, Payload::Retry);
+            }
+            Some(PauseChoice::Settings) => stack.push(State::Settings),
+            Some(PauseChoice::Menu) => {
+                stack.pop();
+                stack.replace(State::Menu);
+            }
+            None => {}
+        }
+    }
+}
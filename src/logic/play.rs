@@ -1,30 +1,379 @@
-use winit::event::{ElementState, KeyEvent, MouseButton};
-use crate::logic::victory::VictoryLogic;
-use crate::sprint_the_game::StateTransition;
+use std::collections::VecDeque;
+use std::mem;
+
+use crate::logic::event::GameEvent;
+use crate::logic::input::InputAction;
+use crate::logic::play::level::{Grid, Level, Tile};
+use crate::logic::victory::LevelResult;
+use crate::logic::scene::{Context, Scene};
+use crate::sprint_the_game::{Payload, State, StateStack};
 
 pub mod level;
 
-pub struct PlayLogic {}
+// The latest slide, the rules move the runner at once and renderers animate it over the slide's age
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slide {
+    pub from: (u32, u32),
+    pub to: (u32, u32),
+    pub age: f32,
+    pub previous_age: f32,
+}
+
+// Ten seconds of ticks can be rewound, holding the key speeds the rewind up to four times over two seconds
+
+const REWIND_TICKS: usize = 1200;
+const REWIND_MAX_SPEED: f32 = 4.0;
+const REWIND_RAMP: f32 = 2.0;
+
+// What a rewind restores, taken once per tick, the clock keeps running
+
+#[derive(Debug, Clone)]
+struct Snapshot {
+    runner: Option<(u32, u32)>,
+    slide: Option<Slide>,
+    moves: u32,
+    trail: Vec<(u32, u32)>,
+}
+
+pub struct PlayLogic {
+    current_level_id: u32,
+
+    pub level: Option<Level>,
+    pub playtest: bool,
+    pub runner: Option<(u32, u32)>,
+
+    // Where the runner was before the latest tick, renderers interpolate from it to runner
+    pub previous_runner: Option<(u32, u32)>,
+    pub slide: Option<Slide>,
+
+    pub moves: u32,
+    pub elapsed: f32,
+    pub rewinding: bool,
+
+    revision: u32,
+    trail: Vec<(u32, u32)>,
+    history: VecDeque<Snapshot>,
+    rewind_time: f32,
+    rewind_ticks: f32,
+    actions: Vec<InputAction>,
+    completed: bool,
+    pause: bool,
+}
 
 impl PlayLogic {
     pub fn new() -> Self {
-        return Self {};
+        return Self {
+            current_level_id: 0,
+            level: None,
+            playtest: false,
+            runner: None,
+            previous_runner: None,
+            slide: None,
+            moves: 0,
+            elapsed: 0.0,
+            rewinding: false,
+            revision: 0,
+            trail: Vec::new(),
+            history: VecDeque::new(),
+            rewind_time: 0.0,
+            rewind_ticks: 0.0,
+            actions: Vec::new(),
+            completed: false,
+            pause: false,
+        };
     }
 
     pub fn get_current_level_id(&self) -> u32 {
-        return 0;
+        return self.current_level_id;
+    }
+
+    pub fn set_current_level_id(&mut self, id: u32) {
+        self.current_level_id = id;
+        self.level = Level::builtin(id);
+
+        self.reload_current_level();
+    }
+
+    // Changes whenever the level, the runner or the move count changes, so renderers can skip rebuilding unchanged frames
+
+    pub fn revision(&self) -> u32 {
+        return self.revision;
+    }
+
+    pub fn reload_current_level(&mut self) {
+        self.runner = self.level.as_ref().and_then(|level| level.find(Tile::Start));
+        self.previous_runner = self.runner;
+        self.slide = None;
+        self.revision = self.revision.wrapping_add(1);
+        self.trail.clear();
+        self.actions.clear();
+        self.moves = 0;
+        self.elapsed = 0.0;
+        self.completed = false;
+        self.rewinding = false;
+        self.history.clear();
+
+        self.record();
+    }
+
+    pub fn move_to_next_level(&mut self) {
+        self.set_current_level_id(self.current_level_id + 1);
+    }
+
+    pub fn complete_level(&mut self) {
+        self.completed = true;
+    }
+
+    pub fn is_completed(&self) -> bool {
+        return self.completed;
+    }
+
+    // Every slide that moves the runner counts, running into a spike sends it back to the start
+
+    // On hex grids left and right slide down the sides and the diagonal actions slide up them, square grids ignore the diagonals
+
+    fn direction(&self, action: InputAction) -> Option<(i32, i32)> {
+        let grid = self.level.as_ref().map_or(Grid::Square, |level| level.grid);
+
+        return match (grid, action) {
+            (_, InputAction::MoveUp) => Some((0, -1)),
+            (_, InputAction::MoveDown) => Some((0, 1)),
+            (Grid::Square, InputAction::MoveLeft) => Some((-1, 0)),
+            (Grid::Square, InputAction::MoveRight) => Some((1, 0)),
+            (Grid::Hex, InputAction::MoveLeft) => Some((-1, 1)),
+            (Grid::Hex, InputAction::MoveRight) => Some((1, 1)),
+            (Grid::Hex, InputAction::MoveUpLeft) => Some((-1, -1)),
+            (Grid::Hex, InputAction::MoveUpRight) => Some((1, -1)),
+            _ => None,
+        };
+    }
+
+    fn step(&mut self, direction: (i32, i32), context: &mut Context) {
+        let (Some(level), Some(runner)) = (&self.level, self.runner) else {
+            return;
+        };
+
+        if self.completed {
+            return;
+        }
+
+        let target = level.slide(runner, direction);
+
+        if target == runner {
+            return;
+        }
+
+        let tile = level.get(target);
+
+        self.runner = if tile == Tile::Spike { level.find(Tile::Start) } else { Some(target) };
+        self.slide = Some(Slide { from: runner, to: target, age: 0.0, previous_age: 0.0 });
+        self.trail.push(runner);
+        self.moves = self.trail.len() as u32;
+        self.revision = self.revision.wrapping_add(1);
+
+        if tile == Tile::Spike && !self.playtest {
+            context.events.publish(GameEvent::RunnerDied(self.current_level_id));
+        }
+
+        // The victory screen opens on this tick and freezes the level below it, so the winning slide is not animated
+
+        if tile == Tile::Goal {
+            self.slide = None;
+            self.complete_level();
+        }
     }
 
-    pub fn set_current_level_id(&mut self, id: u32) {}
+    fn undo(&mut self) {
+        if self.completed {
+            return;
+        }
 
-    pub fn reload_current_level(&mut self) {}
+        if let Some(previous) = self.trail.pop() {
+            self.runner = Some(previous);
+            self.slide = None;
+            self.moves = self.trail.len() as u32;
+            self.revision = self.revision.wrapping_add(1);
+        }
+    }
+
+    fn record(&mut self) {
+        if self.history.len() == REWIND_TICKS {
+            self.history.pop_front();
+        }
+
+        self.history.push_back(Snapshot {
+            runner: self.runner,
+            slide: self.slide,
+            moves: self.moves,
+            trail: self.trail.clone(),
+        });
+    }
+
+    fn set_rewinding(&mut self, rewinding: bool) {
+        if self.rewinding != rewinding {
+            self.rewinding = rewinding;
+            self.rewind_time = 0.0;
+            self.rewind_ticks = 0.0;
+            self.revision = self.revision.wrapping_add(1);
+        }
+    }
+
+    // Steps back through as many ticks as the rewind speed allows and restores the last of them, stopping at the oldest one
+
+    fn rewind(&mut self, delta_time: f32) {
+        self.rewind_time += delta_time;
+        self.rewind_ticks += (1.0 + (REWIND_MAX_SPEED - 1.0) * self.rewind_time / REWIND_RAMP).min(REWIND_MAX_SPEED);
+
+        while self.rewind_ticks >= 1.0 && self.history.len() > 1 {
+            self.history.pop_back();
+            self.rewind_ticks -= 1.0;
+        }
+
+        if self.history.len() <= 1 {
+            self.rewind_ticks = 0.0;
+        }
+
+        let Some(snapshot) = self.history.back().cloned() else {
+            return;
+        };
 
-    pub fn move_to_next_level(&mut self) {}
+        if self.runner != snapshot.runner || self.moves != snapshot.moves {
+            self.revision = self.revision.wrapping_add(1);
+        }
+
+        self.runner = snapshot.runner;
+        self.slide = snapshot.slide.map(|slide| Slide { previous_age: slide.age, ..slide });
+        self.moves = snapshot.moves;
+        self.trail = snapshot.trail;
+    }
+}
+
+impl Default for PlayLogic {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Scene for PlayLogic {
+    fn enter(&mut self, payload: Option<Payload>, context: &mut Context) {
+        match payload {
+            Some(Payload::Level(id)) => {
+                self.playtest = false;
+                self.set_current_level_id(id);
+            }
+            Some(Payload::NextLevel) => self.move_to_next_level(),
+            Some(Payload::Retry) => self.reload_current_level(),
+            Some(Payload::Playtest(level)) => {
+                self.level = Some(level);
+                self.playtest = true;
+                self.reload_current_level();
+
+                return;
+            }
+            _ => return,
+        }
+
+        if !self.playtest {
+            context.events.publish(GameEvent::LevelStarted(self.current_level_id));
+        }
+    }
+
+    fn process_action(&mut self, action: InputAction) {
+        match action {
+            InputAction::MoveUp | InputAction::MoveDown | InputAction::MoveLeft | InputAction::MoveRight | InputAction::MoveUpLeft | InputAction::MoveUpRight
+            | InputAction::Undo | InputAction::Restart => self.actions.push(action),
+            InputAction::Rewind => self.set_rewinding(true),
+            InputAction::RewindRelease => self.set_rewinding(false),
+            InputAction::Back => self.pause = true,
+            _ => {}
+        }
+    }
 
-    pub fn process_keyboard(&mut self, key_event: KeyEvent) {}
+    fn update(&mut self, delta_time: f32, stack: &mut StateStack, context: &mut Context) {
+        self.previous_runner = self.runner;
 
-    pub fn process_mouse(&mut self, element_state: ElementState, mouse_button: MouseButton) {}
+        // While the rewind key is held the history plays backwards and moves are dropped
 
+        if self.rewinding {
+            self.actions.clear();
+            self.rewind(delta_time);
+        } else {
+            if let Some(slide) = &mut self.slide {
+                slide.previous_age = slide.age;
+                slide.age += delta_time;
+            }
 
-    pub fn update(&mut self, delta_time: f32, transition: &mut StateTransition, victory: &mut VictoryLogic) {}
+            // Moves are applied on the tick after they arrive, so they can publish events like any other change
+
+            for action in mem::take(&mut self.actions) {
+                match action {
+                    InputAction::Undo => self.undo(),
+                    InputAction::Restart => self.reload_current_level(),
+                    _ => {
+                        if let Some(direction) = self.direction(action) {
+                            self.step(direction, context);
+                        }
+                    }
+                }
+            }
+
+            self.record();
+        }
+
+        // The rewind key is released in whichever scene comes next, so leaving the level stops the rewind
+
+        if self.pause || self.completed {
+            self.set_rewinding(false);
+        }
+
+        // A playtest goes straight back to the editor below it, without pausing or recording progress
+
+        if self.playtest && (self.pause || self.completed) {
+            self.pause = false;
+            self.completed = false;
+
+            stack.pop();
+
+            return;
+        }
+
+        if self.pause {
+            self.pause = false;
+
+            context.events.publish(GameEvent::Paused(self.current_level_id));
+            stack.push_with(State::Pause, Payload::Level(self.current_level_id));
+
+            return;
+        }
+
+        if !self.completed {
+            self.elapsed += delta_time;
+
+            return;
+        }
+
+        let Some(level) = &self.level else {
+            return;
+        };
+
+        self.completed = false;
+
+        let result = LevelResult {
+            level_id: self.current_level_id,
+            name: level.name.clone(),
+            moves: self.moves,
+            time: self.elapsed,
+            stars: level.stars_for(self.moves),
+        };
+
+        context.save.record_completion(result.level_id, result.moves, result.time, result.stars);
+
+        if let Err(error) = context.save.save(&context.save_path) {
+            eprintln!("{}", error);
+        }
+
+        context.events.publish(GameEvent::LevelWon(result.clone()));
+        stack.push_with(State::Victory, Payload::Result(result));
+    }
 }
\ No newline at end of file
@@ -1 +1,305 @@
-pub struct LevenLogic {}
\ No newline at end of file
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{data_dir, write_atomic};
+use crate::logic::menu::UnlockRule;
+
+pub const LEVEL_VERSION: u32 = 1;
+
+// Larger levels would overflow the 16 bit vertex indices the renderers draw them with
+
+pub const MAX_SIZE: (u32, u32) = (64, 40);
+
+// The shipped levels in play order, a level's id is its index here
+
+const BUILTIN: [&str; 6] = [
+    include_str!("levels/level_0.toml"),
+    include_str!("levels/level_1.toml"),
+    include_str!("levels/level_2.toml"),
+    include_str!("levels/level_3.toml"),
+    include_str!("levels/level_4.toml"),
+    include_str!("levels/level_5.toml"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Start,
+    Goal,
+    Spike,
+}
+
+impl Tile {
+    pub const ALL: [Tile; 5] = [Tile::Empty, Tile::Wall, Tile::Start, Tile::Goal, Tile::Spike];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Tile::Empty => "Empty",
+            Tile::Wall => "Wall",
+            Tile::Start => "Start",
+            Tile::Goal => "Goal",
+            Tile::Spike => "Spike",
+        };
+    }
+
+    pub fn symbol(&self) -> char {
+        return match self {
+            Tile::Empty => '.',
+            Tile::Wall => '#',
+            Tile::Start => 'S',
+            Tile::Goal => 'G',
+            Tile::Spike => '^',
+        };
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        return Self::ALL.into_iter().find(|tile| tile.symbol() == symbol);
+    }
+}
+
+// Hex levels use flat topped cells in columns, every odd column sits half a cell lower than its neighbours
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Grid {
+    #[default]
+    Square,
+    Hex,
+}
+
+// On disk a level is a TOML file with one string per row and one symbol per tile
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LevelFile {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    stars: Vec<u32>,
+    #[serde(default)]
+    unlock: UnlockRule,
+    #[serde(default)]
+    grid: Grid,
+    rows: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+
+    // Move counts to beat for each star after the one every completion earns, loosest first
+    pub stars: Vec<u32>,
+    pub unlock: UnlockRule,
+    pub grid: Grid,
+
+    tiles: Vec<Tile>,
+}
+
+impl Level {
+    pub fn new(width: u32, height: u32) -> Self {
+        return Self {
+            name: String::new(),
+            width,
+            height,
+            stars: Vec::new(),
+            unlock: UnlockRule::Previous,
+            grid: Grid::Square,
+            tiles: vec![Tile::Empty; (width * height) as usize],
+        };
+    }
+
+    pub fn builtin_count() -> u32 {
+        return BUILTIN.len() as u32;
+    }
+
+    pub fn builtin(id: u32) -> Option<Self> {
+        let content = BUILTIN.get(id as usize)?;
+
+        return Some(Self::parse(content).expect("Built-in level failed to parse"));
+    }
+
+    pub fn directory() -> PathBuf {
+        return data_dir().join("levels");
+    }
+
+    pub fn list(directory: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(directory) else {
+            return Vec::new();
+        };
+
+        let mut paths = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "toml"))
+            .collect::<Vec<PathBuf>>();
+
+        paths.sort();
+
+        return paths;
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let file: LevelFile = toml::from_str(content).map_err(|error| error.to_string())?;
+
+        if file.version > LEVEL_VERSION {
+            return Err(format!("level was written by a newer version ({} > {})", file.version, LEVEL_VERSION));
+        }
+
+        let height = file.rows.len() as u32;
+        let width = file.rows.first().map_or(0, |row| row.chars().count()) as u32;
+
+        if width == 0 || height == 0 {
+            return Err("level has no tiles".to_string());
+        }
+
+        if width > MAX_SIZE.0 || height > MAX_SIZE.1 {
+            return Err(format!("level is {}x{} tiles, the largest supported size is {}x{}", width, height, MAX_SIZE.0, MAX_SIZE.1));
+        }
+
+        let mut tiles = Vec::with_capacity((width * height) as usize);
+
+        for (y, row) in file.rows.iter().enumerate() {
+            if row.chars().count() as u32 != width {
+                return Err(format!("row {} is {} tiles wide, expected {}", y + 1, row.chars().count(), width));
+            }
+
+            for (x, symbol) in row.chars().enumerate() {
+                tiles.push(Tile::from_symbol(symbol).ok_or_else(|| format!("unknown tile '{}' at row {}, column {}", symbol, y + 1, x + 1))?);
+            }
+        }
+
+        return Ok(Self {
+            name: file.name,
+            width,
+            height,
+            stars: file.stars,
+            unlock: file.unlock,
+            grid: file.grid,
+            tiles,
+        });
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+
+        return Self::parse(&content).map_err(|error| format!("Failed to parse {}: {}", path.display(), error));
+    }
+
+    pub fn to_text(&self) -> Result<String, String> {
+        let file = LevelFile {
+            version: LEVEL_VERSION,
+            name: self.name.clone(),
+            stars: self.stars.clone(),
+            unlock: self.unlock,
+            grid: self.grid,
+            rows: self.tiles
+                .chunks(self.width as usize)
+                .map(|row| row.iter().map(|tile| tile.symbol()).collect())
+                .collect(),
+        };
+
+        return toml::to_string_pretty(&file).map_err(|error| format!("Failed to serialize level: {}", error));
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        return write_atomic(path, &self.to_text()?);
+    }
+
+    pub fn contains(&self, (x, y): (i32, i32)) -> bool {
+        return x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height;
+    }
+
+    pub fn get(&self, (x, y): (u32, u32)) -> Tile {
+        return self.tiles[(y * self.width + x) as usize];
+    }
+
+    pub fn set(&mut self, (x, y): (u32, u32), tile: Tile) -> bool {
+        let index = (y * self.width + x) as usize;
+        let changed = self.tiles[index] != tile;
+
+        self.tiles[index] = tile;
+
+        return changed;
+    }
+
+    // Keeps the tiles of the overlapping top left area, new tiles start empty
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        let mut resized = Self::new(width, height);
+
+        for y in 0..height.min(self.height) {
+            for x in 0..width.min(self.width) {
+                resized.set((x, y), self.get((x, y)));
+            }
+        }
+
+        self.width = width;
+        self.height = height;
+        self.tiles = resized.tiles;
+    }
+
+    pub fn tiles(&self) -> &[Tile] {
+        return &self.tiles;
+    }
+
+    pub fn find(&self, tile: Tile) -> Option<(u32, u32)> {
+        let index = self.tiles.iter().position(|other| *other == tile)? as u32;
+
+        return Some((index % self.width, index / self.width));
+    }
+
+    // Directions are (column, row) steps, on hex grids a sideways step goes to the upper or lower neighbour in that column
+
+    pub fn neighbour(&self, (x, y): (u32, u32), (dx, dy): (i32, i32)) -> Option<(u32, u32)> {
+        let dy = match self.grid {
+            Grid::Hex if dx != 0 => {
+                let lower = x % 2;
+
+                if dy < 0 { lower as i32 - 1 } else { lower as i32 }
+            }
+            _ => dy,
+        };
+
+        let next = (x as i32 + dx, y as i32 + dy);
+
+        if !self.contains(next) {
+            return None;
+        }
+
+        return Some((next.0 as u32, next.1 as u32));
+    }
+
+    pub fn directions(&self) -> &'static [(i32, i32)] {
+        return match self.grid {
+            Grid::Square => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Grid::Hex => &[(0, -1), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)],
+        };
+    }
+
+    // The runner slides until a wall or the edge stops it, goals and spikes catch it on the way
+
+    pub fn slide(&self, from: (u32, u32), (dx, dy): (i32, i32)) -> (u32, u32) {
+        let mut position = from;
+
+        loop {
+            let Some(next) = self.neighbour(position, (dx, dy)).filter(|next| self.get(*next) != Tile::Wall) else {
+                return position;
+            };
+
+            position = next;
+
+            if matches!(self.get(position), Tile::Goal | Tile::Spike) {
+                return position;
+            }
+        }
+    }
+
+    pub fn stars_for(&self, moves: u32) -> u32 {
+        return 1 + self.stars.iter().filter(|limit| moves <= **limit).count() as u32;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use std::any::Any;
+use std::path::PathBuf;
+
+use crate::logic::event::{EventBus, GameEvent};
+use crate::logic::input::{InputAction, KeyCapture, TextInput};
+use crate::save::SaveGame;
+use crate::sprint_the_game::{Payload, StateStack};
+
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        return self;
+    }
+}
+
+// Data shared between scenes, scenes talk to each other through payloads and events instead
+
+pub struct Context {
+    pub save: SaveGame,
+    pub save_path: PathBuf,
+    pub events: EventBus,
+}
+
+pub trait Scene: AsAny + 'static {
+    fn enter(&mut self, _payload: Option<Payload>, _context: &mut Context) {}
+
+    fn capture(&self) -> KeyCapture {
+        return KeyCapture::Actions;
+    }
+
+    fn process_action(&mut self, _action: InputAction) {}
+
+    fn process_key(&mut self, _key: String) {}
+
+    fn process_text(&mut self, _input: TextInput) {}
+
+    fn process_cursor(&mut self, _position: (f32, f32)) {}
+
+    // The cursor left the window or the window lost focus
+
+    fn process_cursor_left(&mut self) {}
+
+    fn layout(&mut self, _size: (u32, u32)) {}
+
+    fn update(&mut self, _delta_time: f32, _stack: &mut StateStack, _context: &mut Context) {}
+
+    fn handle_event(&mut self, _event: &GameEvent, _context: &Context) {}
+}
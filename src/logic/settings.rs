@@ -0,0 +1,120 @@
+use crate::logic::event::GameEvent;
+use crate::logic::input::InputAction;
+use crate::settings::{Settings, Theme, WindowMode};
+use crate::logic::scene::{Context, Scene};
+use crate::sprint_the_game::StateStack;
+
+const ROW_COUNT: usize = 10;
+
+pub struct SettingsLogic {
+    pub settings: Settings,
+    pub window_override: Option<WindowMode>,
+    pub selected: usize,
+
+    changed: bool,
+    leave: bool,
+}
+
+impl SettingsLogic {
+    pub fn new() -> Self {
+        return Self {
+            settings: Settings::load_or_default(&Settings::path()),
+            window_override: None,
+            selected: 0,
+            changed: false,
+            leave: false,
+        };
+    }
+
+    // A window mode given on the command line only lasts for this session, it is never written to the settings file
+
+    pub fn effective(&self) -> Settings {
+        let mut settings = self.settings.clone();
+
+        if let Some(window_mode) = self.window_override {
+            settings.window_mode = window_mode;
+        }
+
+        return settings;
+    }
+
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let on_off = |value: bool| if value { "On".to_string() } else { "Off".to_string() };
+
+        return vec![
+            ("Window mode", format!("{:?}", self.effective().window_mode)),
+            ("VSync", on_off(self.settings.vsync)),
+            ("Animations", on_off(self.settings.animations)),
+            ("Animation speed", format!("{:.2}x", self.settings.animation_speed)),
+            ("Slide easing", format!("{:?}", self.settings.slide_easing)),
+            ("Volume", format!("{}%", (self.settings.volume * 100.0).round())),
+            ("Colour theme", format!("{:?}", self.settings.theme)),
+            ("Show FPS", on_off(self.settings.show_fps)),
+            ("Transition", format!("{:?}", self.settings.transition)),
+            ("Transition time", format!("{:.1} s", self.settings.transition_duration)),
+        ];
+    }
+
+    fn adjust(&mut self, step: i32) {
+        let settings = &mut self.settings;
+
+        match self.selected {
+            0 => settings.window_mode = match self.window_override.take().unwrap_or(settings.window_mode) {
+                WindowMode::Windowed => WindowMode::Fullscreen,
+                WindowMode::Fullscreen => WindowMode::Windowed,
+            },
+            1 => settings.vsync = !settings.vsync,
+            2 => settings.animations = !settings.animations,
+            3 => settings.animation_speed = (settings.animation_speed + 0.25 * step as f32).clamp(0.25, 3.0),
+            4 => settings.slide_easing = settings.slide_easing.cycle(step),
+            5 => settings.volume = (settings.volume + 0.1 * step as f32).clamp(0.0, 1.0),
+            6 => settings.theme = match settings.theme {
+                Theme::Classic => Theme::HighContrast,
+                Theme::HighContrast => Theme::Classic,
+            },
+            7 => settings.show_fps = !settings.show_fps,
+            8 => settings.transition = settings.transition.cycle(step),
+            9 => settings.transition_duration = (settings.transition_duration + 0.1 * step as f32).clamp(0.1, 2.0),
+            _ => {}
+        }
+
+        self.changed = true;
+    }
+}
+
+impl Default for SettingsLogic {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Scene for SettingsLogic {
+    fn process_action(&mut self, action: InputAction) {
+        match action {
+            InputAction::MoveUp => self.selected = self.selected.saturating_sub(1),
+            InputAction::MoveDown => self.selected = (self.selected + 1).min(ROW_COUNT - 1),
+            InputAction::MoveLeft => self.adjust(-1),
+            InputAction::MoveRight | InputAction::Confirm => self.adjust(1),
+            InputAction::Back => self.leave = true,
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, _delta_time: f32, stack: &mut StateStack, context: &mut Context) {
+        if self.changed {
+            self.changed = false;
+
+            context.events.publish(GameEvent::SettingsChanged(self.effective()));
+        }
+
+        if self.leave {
+            self.leave = false;
+
+            if let Err(error) = self.settings.save(&Settings::path()) {
+                eprintln!("{}", error);
+            }
+
+            stack.pop();
+        }
+    }
+}
@@ -1,17 +1,113 @@
-use winit::event::{ElementState, KeyEvent, MouseButton};
-use crate::logic::play::PlayLogic;
-use crate::sprint_the_game::StateTransition;
+use crate::logic::input::InputAction;
+use crate::logic::panel::{Arrangement, ButtonPanel};
+use crate::logic::play::level::Level;
+use crate::logic::scene::{Context, Scene};
+use crate::sprint_the_game::{Payload, State, StateStack};
 
-pub struct VictoryLogic {}
+const BUTTON_SIZE: (u32, u32) = (220, 70);
+const BUTTON_GAP: u32 = 40;
+const PANEL_SIZE: (u32, u32) = (3 * 220 + 4 * 40, 420);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelResult {
+    pub level_id: u32,
+    pub name: String,
+    pub moves: u32,
+    pub time: f32,
+    pub stars: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VictoryChoice {
+    NextLevel,
+    Retry,
+    Menu,
+}
+
+const BUTTONS: [(VictoryChoice, &str); 3] = [(VictoryChoice::NextLevel, "Next Level"), (VictoryChoice::Retry, "Retry"), (VictoryChoice::Menu, "Menu")];
+
+pub struct VictoryLogic {
+    pub result: Option<LevelResult>,
+    pub panel: ButtonPanel<VictoryChoice>,
+
+    chosen: Option<VictoryChoice>,
+}
 
 impl VictoryLogic {
     pub fn new() -> Self {
-        return Self {};
+        let mut panel = ButtonPanel::new(Arrangement::Row, PANEL_SIZE, BUTTON_SIZE, BUTTON_GAP);
+
+        panel.set_buttons(&BUTTONS);
+
+        return Self {
+            result: None,
+            panel,
+            chosen: None,
+        };
+    }
+
+    // There is nothing after the last level, so its victory screen only offers a retry or the menu
+
+    pub fn set_result(&mut self, result: LevelResult) {
+        if result.level_id + 1 < Level::builtin_count() {
+            self.panel.set_buttons(&BUTTONS);
+        } else {
+            self.panel.set_buttons(&BUTTONS[1..]);
+        }
+
+        self.result = Some(result);
+        self.chosen = None;
+    }
+}
+
+impl Default for VictoryLogic {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Scene for VictoryLogic {
+    fn enter(&mut self, payload: Option<Payload>, _context: &mut Context) {
+        if let Some(Payload::Result(result)) = payload {
+            self.set_result(result);
+        }
     }
 
-    pub fn process_keyboard(&mut self, key_event: KeyEvent) {}
+    fn layout(&mut self, size: (u32, u32)) {
+        self.panel.layout(size);
+    }
 
-    pub fn process_mouse(&mut self, element_state: ElementState, mouse_button: MouseButton) {}
+    fn process_cursor(&mut self, position: (f32, f32)) {
+        self.panel.process_cursor(position);
+    }
 
-    pub fn update(&mut self, delta_time: f32, transition: &mut StateTransition, play: &mut PlayLogic) {}
-}
\ No newline at end of file
+    fn process_action(&mut self, action: InputAction) {
+        match action {
+            InputAction::Restart => self.chosen = Some(VictoryChoice::Retry),
+            InputAction::Back => self.chosen = Some(VictoryChoice::Menu),
+            _ => {
+                if let Some(choice) = self.panel.process_action(action) {
+                    self.chosen = Some(choice);
+                }
+            }
+        }
+    }
+
+    fn update(&mut self, _delta_time: f32, stack: &mut StateStack, _context: &mut Context) {
+        match self.chosen.take() {
+            Some(VictoryChoice::NextLevel) => {
+                stack.pop();
+                stack.replace_with(State::Play, Payload::NextLevel);
+            }
+            Some(VictoryChoice::Retry) => {
+                stack.pop();
+                stack.replace_with(State::Play, Payload::Retry);
+            }
+            Some(VictoryChoice::Menu) => {
+                stack.pop();
+                stack.replace(State::Menu);
+            }
+            None => {}
+        }
+    }
+}
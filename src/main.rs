@@ -1,9 +1,11 @@
+use std::env;
+use std::process;
 use std::time::{
     Duration,
     Instant,
 };
 
-use wgpu::{Adapter, Device, Instance, Queue, Surface, SurfaceConfiguration, TextureFormat};
+use wgpu::{Device, Instance, PresentMode, Queue, Surface, SurfaceConfiguration, TextureFormat};
 
 use winit::{
     event::{
@@ -16,6 +18,7 @@ use winit::{
         PumpStatus,
     },
     window::{
+        Fullscreen,
         Window,
         WindowBuilder,
     },
@@ -25,13 +28,15 @@ use winit::{
     },
 };
 use winit::window::WindowButtons;
-use crate::sprint_the_game::Application;
+use sprint_the_game::cli::{Options, USAGE};
+use sprint_the_game::settings::{Settings, WindowMode};
+use sprint_the_game::sprint_the_game::Application;
 
-mod sprint_the_game;
-pub mod logic;
-pub mod renderer;
+const TICK_RATE: f32 = 120.0;
+const TICK_DELTA: f32 = 1.0 / TICK_RATE;
+const MAX_FRAME_TIME: f32 = 0.25;
 
-async fn build_backend(window: &Window) -> (Instance, Surface, SurfaceConfiguration, Adapter, Device, Queue) {
+async fn build_backend(window: &Window) -> (Instance, Surface<'_>, SurfaceConfiguration, Device, Queue) {
     let instance = wgpu::Instance::default();
 
     let surface = instance.create_surface(window).unwrap();
@@ -69,16 +74,43 @@ async fn build_backend(window: &Window) -> (Instance, Surface, SurfaceConfigurat
 
     surface.configure(&device, &config);
 
-    return (instance, surface, config, adapter, device, queue);
+    return (instance, surface, config, device, queue);
 }
 
+fn apply_settings(settings: &Settings, window: &Window, surface: &Surface, device: &Device, config: &mut SurfaceConfiguration) {
+    match settings.window_mode {
+        WindowMode::Windowed => window.set_fullscreen(None),
+        WindowMode::Fullscreen => window.set_fullscreen(Some(Fullscreen::Borderless(None))),
+    }
+
+    config.present_mode = if settings.vsync { PresentMode::AutoVsync } else { PresentMode::AutoNoVsync };
+
+    surface.configure(device, config);
+}
 
 fn main() {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+
+            process::exit(2);
+        }
+    };
+
+    if options.help {
+        println!("{}", USAGE);
+
+        return;
+    }
+
+    let (width, height) = options.size.unwrap_or((1280, 720));
+
     let mut event_loop = EventLoop::new().unwrap();
     let builder = WindowBuilder::new();
     let window = builder
         .with_title("Sprint The Game")
-        .with_inner_size(LogicalSize::new(1280, 720))
+        .with_inner_size(LogicalSize::new(width, height))
         .with_resizable(false)
         .with_enabled_buttons(WindowButtons::CLOSE | WindowButtons::MINIMIZE)
         .build(&event_loop).unwrap();
@@ -95,22 +127,42 @@ fn main() {
         });
     }
 
-    let (_instance, surface, mut config, adapter, device, queue) = pollster::block_on(build_backend(&window));
+    let (_instance, surface, mut config, device, queue) = pollster::block_on(build_backend(&window));
+
+    let mut application = Application::new(&device, &queue, &config, window.scale_factor() as f32);
+
+    if let Err(error) = application.apply_options(&options) {
+        eprintln!("error: {}", error);
 
-    let mut application = Application::new(&device, &surface, &adapter, &queue, &config);
+        process::exit(2);
+    }
+
+    apply_settings(&application.settings(), &window, &surface, &device, &mut config);
 
     let start = Instant::now();
     let mut last_tick_time = start.elapsed();
     let mut last_timer_time = start.elapsed();
+    let mut accumulator = 0.0;
 
     let mut frames = 0;
 
     'main: loop {
         let now = start.elapsed();
-        let delta_time = (now - last_tick_time).as_secs_f32();
+        let frame_time = (now - last_tick_time).as_secs_f32().min(MAX_FRAME_TIME);
         last_tick_time = now;
 
-        application.update(delta_time);
+        accumulator += frame_time;
+
+        while accumulator >= TICK_DELTA {
+            application.update(TICK_DELTA);
+            accumulator -= TICK_DELTA;
+        }
+
+        if let Some(settings) = application.take_settings_change() {
+            apply_settings(&settings, &window, &surface, &device, &mut config);
+        }
+
+        application.interpolate(accumulator / TICK_DELTA, &device, &queue);
 
         let timeout = Some(Duration::ZERO);
         let status = event_loop.pump_events(timeout, |event, target| {
@@ -127,7 +179,17 @@ fn main() {
 
                             surface.configure(&device, &config);
 
-                            application.process_resize((new_size.width, new_size.height), &queue);
+                            application.process_resize((new_size.width, new_size.height), window.scale_factor() as f32, &device, &queue);
+
+                            window.request_redraw();
+                        }
+                        WindowEvent::ScaleFactorChanged {
+                            scale_factor,
+                            ..
+                        } => {
+                            let size = window.inner_size();
+
+                            application.process_resize((size.width, size.height), scale_factor as f32, &device, &queue);
 
                             window.request_redraw();
                         }
@@ -137,6 +199,19 @@ fn main() {
                             event,
                             ..
                         } => application.process_keyboard(event),
+                        WindowEvent::CursorMoved {
+                            position,
+                            ..
+                        } => {
+                            let position: LogicalPosition<f32> = position.to_logical(window.scale_factor());
+
+                            application.process_cursor((position.x, position.y));
+                        }
+                        WindowEvent::CursorLeft { .. } | WindowEvent::Focused(false) => application.process_cursor_left(),
+                        WindowEvent::MouseWheel {
+                            delta,
+                            ..
+                        } => application.process_wheel(delta),
                         WindowEvent::MouseInput {
                             state,
                             button,
@@ -153,12 +228,18 @@ fn main() {
             break 'main;
         }
 
+        if application.should_quit() {
+            break 'main;
+        }
+
         frames += 1;
 
         if (now - last_timer_time).as_secs_f32() > 1.0 {
             last_timer_time += Duration::from_secs_f32(1.0);
 
-            println!("Sprint The Game is running at {} fps", frames);
+            if application.settings().show_fps {
+                println!("Sprint The Game is running at {} fps", frames);
+            }
 
             frames = 0;
         }
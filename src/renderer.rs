@@ -1,29 +1,46 @@
+use std::collections::BTreeMap;
 use std::rc::Rc;
 use bytemuck::{Pod, Zeroable};
-use wgpu::{Adapter, Device, LoadOp, Operations, Queue, RenderPassColorAttachment, RenderPassDescriptor, Surface, SurfaceConfiguration};
+use wgpu::{CommandEncoder, Device, LoadOp, Operations, Queue, RenderPass, RenderPassColorAttachment, RenderPassDescriptor, Surface, SurfaceConfiguration, TextureView};
 
 use crate::logic::{
+    bindings::BindingsLogic,
     edit::EditLogic,
     menu::MenuLogic,
+    pause::PauseLogic,
     play::PlayLogic,
+    settings::SettingsLogic,
     victory::VictoryLogic,
+    Logic,
 };
 
 use crate::renderer::{
+    bindings::BindingsRenderer,
     edit::EditRenderer,
     menu::MenuRenderer,
+    pause::PauseRenderer,
     play::PlayRenderer,
+    scene::SceneRenderer,
+    settings::SettingsRenderer,
+    transition::TransitionRenderer,
     victory::VictoryRenderer,
 };
-use crate::renderer::utils::palette::{BLACK, INCREASED_DARKBLUE};
+use crate::renderer::utils::palette::BLACK;
 use crate::renderer::utils::pipeline::{ColorPipeline, TexturePipeline};
 
+use crate::settings::Settings;
 use crate::sprint_the_game::State;
 
 pub mod menu;
 pub mod play;
+pub mod pause;
 pub mod victory;
+pub mod panel;
 pub mod edit;
+pub mod bindings;
+pub mod settings;
+pub mod scene;
+pub mod transition;
 
 pub mod utils;
 
@@ -43,51 +60,132 @@ pub struct TextureVertex {
 }
 
 pub struct Renderer {
-    color: Rc<ColorPipeline>,
-    texture: Rc<TexturePipeline>,
-
-    pub menu: MenuRenderer,
-    pub play: PlayRenderer,
-    pub victory: VictoryRenderer,
-    pub edit: EditRenderer,
+    scenes: BTreeMap<State, Box<dyn SceneRenderer>>,
+    transition: TransitionRenderer,
 }
 
 impl Renderer {
-    pub fn new(menu: &MenuLogic, play: &PlayLogic, victory: &VictoryLogic, edit: &EditLogic, device: &Device, surface: &Surface, adapter: &Adapter, queue: &Queue, config: &SurfaceConfiguration) -> Self {
-        let color = Rc::new(ColorPipeline::new(device, surface, adapter, config));
-        let texture = Rc::new(TexturePipeline::new(device, surface, adapter, config));
-        let menu = MenuRenderer::new(menu, color.clone(), texture.clone(), device, queue, config);
-        let play = PlayRenderer::new(play, color.clone(), texture.clone(), device, surface, adapter, config);
-        let victory = VictoryRenderer::new(victory, color.clone(), texture.clone());
-        let edit = EditRenderer::new(edit, color.clone(), texture.clone());
-
-        return Self {
-            color,
-            texture,
-            menu,
-            play,
-            victory,
-            edit,
+    pub fn new(logic: &Logic, device: &Device, queue: &Queue, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
+        let color = Rc::new(ColorPipeline::new(device, config));
+        let texture = Rc::new(TexturePipeline::new(device, config));
+
+        let mut renderer = Self {
+            scenes: BTreeMap::new(),
+            transition: TransitionRenderer::new(device, config),
         };
+
+        if let Some(menu) = logic.scene::<MenuLogic>(&State::Menu) {
+            renderer.register(State::Menu, MenuRenderer::new(menu, color.clone(), texture.clone(), device, queue, config, scale_factor));
+        }
+
+        if let Some(play) = logic.scene::<PlayLogic>(&State::Play) {
+            renderer.register(State::Play, PlayRenderer::new(play, color.clone(), device, queue, config, scale_factor));
+        }
+
+        if let Some(pause) = logic.scene::<PauseLogic>(&State::Pause) {
+            renderer.register(State::Pause, PauseRenderer::new(pause, color.clone(), device, queue, config, scale_factor));
+        }
+
+        if let Some(victory) = logic.scene::<VictoryLogic>(&State::Victory) {
+            renderer.register(State::Victory, VictoryRenderer::new(victory, color.clone(), device, queue, config, scale_factor));
+        }
+
+        if let Some(edit) = logic.scene::<EditLogic>(&State::Edit) {
+            renderer.register(State::Edit, EditRenderer::new(edit, color.clone(), device, queue, config, scale_factor));
+        }
+
+        if let Some(bindings) = logic.scene::<BindingsLogic>(&State::Bindings) {
+            renderer.register(State::Bindings, BindingsRenderer::new(bindings, color.clone(), device, queue, config, scale_factor));
+        }
+
+        if let Some(settings) = logic.scene::<SettingsLogic>(&State::Settings) {
+            renderer.register(State::Settings, SettingsRenderer::new(settings, color.clone(), device, queue, config, scale_factor));
+        }
+
+        return renderer;
+    }
+
+    pub fn register<T: SceneRenderer>(&mut self, state: State, scene: T) {
+        self.scenes.insert(state, Box::new(scene));
+    }
+
+    pub fn layout(&self, logic: &mut Logic) {
+        for (state, scene) in &self.scenes {
+            if let Some(logic) = logic.get_mut(state) {
+                scene.layout(logic);
+            }
+        }
+    }
+
+    pub fn configure(&mut self, settings: &Settings) {
+        self.transition.configure(settings.transition, settings.transition_duration);
+
+        for scene in self.scenes.values_mut() {
+            scene.configure(settings);
+        }
+    }
+
+    pub fn tick(&mut self, states: &[State], delta_time: f32) {
+        self.transition.tick(states, delta_time);
+    }
+
+    pub fn update(&mut self, states: &[State], logic: &Logic, alpha: f32, device: &Device, queue: &Queue) {
+        for (i, state) in states.iter().enumerate() {
+            // Only the top state is ticked, the ones beneath an overlay are drawn as they were left
+
+            let alpha = if i + 1 == states.len() { alpha } else { 1.0 };
+
+            if let (Some(scene), Some(logic)) = (self.scenes.get_mut(state), logic.get(state)) {
+                scene.update(logic, alpha, device, queue);
+            }
+        }
+
+        self.transition.update(queue);
     }
 
-    pub fn update(&mut self, state: &State, menu: &MenuLogic, play: &PlayLogic, victory: &VictoryLogic, edit: &EditLogic) {
-        match state {
-            State::Menu => self.menu.update(menu),
-            State::Play => self.play.update(play),
-            State::Victory => self.victory.update(victory),
-            State::Edit => self.edit.update(edit),
+    pub fn process_resize(&mut self, (width, height): (u32, u32), scale_factor: f32, device: &Device, queue: &Queue) {
+        for scene in self.scenes.values_mut() {
+            scene.process_resize((width, height), scale_factor, queue);
         }
+
+        self.transition.process_resize((width, height), device);
+    }
+
+    fn begin_pass<'pass>(encoder: &'pass mut CommandEncoder, view: &'pass TextureView) -> RenderPass<'pass> {
+        return encoder.begin_render_pass(&RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(wgpu::Color {
+                        r: BLACK.0 as f64 / 255.0,
+                        g: BLACK.1 as f64 / 255.0,
+                        b: BLACK.2 as f64 / 255.0,
+                        a: 1.0,
+                    }),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
     }
 
-    pub fn process_resize(&mut self, (width, height): (u32, u32), queue: &Queue) {
-        self.menu.process_resize((width, height), queue);
-        self.play.process_resize((width, height), queue);
-        self.victory.process_resize((width, height), queue);
-        self.edit.process_resize((width, height), queue);
+    fn render_states(&self, encoder: &mut CommandEncoder, view: &TextureView, states: &[State]) {
+        let mut pass = Self::begin_pass(encoder, view);
+
+        // Overlays are drawn over the states beneath them, bottom to top
+
+        for state in states {
+            if let Some(scene) = self.scenes.get(state) {
+                scene.render(&mut pass);
+            }
+        }
     }
 
-    pub fn render(&self, state: &State, device: &Device, surface: &Surface, queue: &Queue) {
+    pub fn render(&mut self, states: &[State], device: &Device, surface: &Surface, queue: &Queue) {
         let frame = surface
             .get_current_texture()
             .expect("Failed to acquire next swap chain texture");
@@ -99,33 +197,20 @@ impl Renderer {
                 label: None,
             });
 
-        {
-            let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
-                label: None,
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: Operations {
-                        load: LoadOp::Clear(wgpu::Color {
-                            r: BLACK.0 as f64 / 255.0,
-                            g: BLACK.1 as f64 / 255.0,
-                            b: BLACK.2 as f64 / 255.0,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
+        // During a transition both sides are drawn off screen, then blended onto the frame
 
-            match state {
-                State::Menu => self.menu.render(&mut pass),
-                State::Play => self.play.render(&mut pass),
-                State::Victory => self.victory.render(&mut pass),
-                State::Edit => self.edit.render(&mut pass),
+        if self.transition.is_active() {
+            if self.transition.take_snapshot() {
+                self.render_states(&mut encoder, &self.transition.from.view, self.transition.outgoing());
             }
+
+            self.render_states(&mut encoder, &self.transition.to.view, states);
+
+            let mut pass = Self::begin_pass(&mut encoder, &view);
+
+            self.transition.render(&mut pass);
+        } else {
+            self.render_states(&mut encoder, &view, states);
         }
 
         queue.submit(Some(encoder.finish()));
@@ -0,0 +1,220 @@
+use std::rc::Rc;
+use glam::Mat4;
+use wgpu::{BindGroup, Buffer, Device, Queue, RenderPass, SurfaceConfiguration};
+use wgpu::util::DeviceExt;
+use wgpu_text::glyph_brush::ab_glyph::FontRef;
+use wgpu_text::{BrushBuilder, TextBrush};
+use wgpu_text::glyph_brush::{BuiltInLineBreaker, Layout, Section, Text};
+use winit::dpi::PhysicalPosition;
+
+use crate::logic::bindings::BindingsLogic;
+use crate::logic::scene::Scene;
+use crate::renderer::ColorVertex;
+use crate::renderer::scene::SceneRenderer;
+use crate::renderer::utils::draw_text_box;
+use crate::renderer::utils::palette::{DARKGREY, IVORY, RED, YELLOW};
+use crate::renderer::utils::pipeline::ColorPipeline;
+
+const ROW_TOP: u32 = 150;
+const ROW_HEIGHT: u32 = 45;
+
+pub struct BindingsRenderer {
+    brush: TextBrush<FontRef<'static>>,
+    projection_view_model_uniform: Buffer,
+    vertex_buffer: Buffer,
+    index_buffer: Buffer,
+    indices_count: usize,
+    bind_group: BindGroup,
+
+    size: (u32, u32),
+    scale_factor: f32,
+    selected: Option<usize>,
+    lines: Vec<String>,
+
+    color: Rc<ColorPipeline>,
+}
+
+impl BindingsRenderer {
+    pub fn new(logic: &BindingsLogic, color: Rc<ColorPipeline>, device: &Device, queue: &Queue, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
+        let font: &[u8] = include_bytes!("fonts/BulletTrace7-rppO.ttf");
+        let brush = BrushBuilder::using_font_bytes(font).unwrap().build(
+            device,
+            config.width,
+            config.height,
+            config.format,
+        );
+
+        let size = ((config.width as f32 / scale_factor) as u32, (config.height as f32 / scale_factor) as u32);
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, size.0 as f32, size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+        let projection_view_model_uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Uniform Buffer"),
+            contents: bytemuck::cast_slice(projection_view_model_ref),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Bindings BindGroup"),
+            layout: &color.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: projection_view_model_uniform.as_entire_binding(),
+                },
+            ],
+        });
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build_selection(logic, size, device);
+
+        let mut renderer = Self {
+            brush,
+            projection_view_model_uniform,
+            vertex_buffer,
+            index_buffer,
+            indices_count,
+            bind_group,
+            size,
+            scale_factor,
+            selected: None,
+            lines: Vec::new(),
+            color,
+        };
+
+        renderer.update(logic, 0.0, device, queue);
+
+        return renderer;
+    }
+
+    // Scrolls the list once the selection would fall below the rows that fit above the footer
+
+    fn visible_rows(logic: &BindingsLogic, height: u32) -> (usize, usize) {
+        let count = (height.saturating_sub(ROW_TOP + 100) / ROW_HEIGHT).max(1) as usize;
+        let first = (logic.selected + 1).saturating_sub(count);
+
+        return (first, count);
+    }
+
+    fn build_selection(logic: &BindingsLogic, (width, height): (u32, u32), device: &Device) -> (Buffer, Buffer, usize) {
+        let mut vertex_data: Vec<ColorVertex> = Vec::new();
+        let mut index_data: Vec<u16> = Vec::new();
+
+        let (first, _) = Self::visible_rows(logic, height);
+        let up = ROW_TOP + (logic.selected - first) as u32 * ROW_HEIGHT;
+
+        draw_text_box(&mut vertex_data, &mut index_data, (150, up), (width.saturating_sub(300).max(1), ROW_HEIGHT - 10), 0, 3);
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bindings VertexBuffer"),
+            contents: bytemuck::cast_slice(&vertex_data),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Bindings IndexBuffer"),
+            contents: bytemuck::cast_slice(&index_data),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        return (vertex_buffer, index_buffer, index_data.len());
+    }
+}
+
+impl SceneRenderer for BindingsRenderer {
+    fn update(&mut self, logic: &dyn Scene, _alpha: f32, device: &Device, queue: &Queue) {
+        let logic = logic.as_any().downcast_ref::<BindingsLogic>().expect("BindingsRenderer expects a BindingsLogic");
+
+        let mut lines = Vec::new();
+
+        for (action, keys) in &logic.key_bindings.keys {
+            lines.push(format!("{:?}", action));
+            lines.push(keys.join(", "));
+        }
+
+        let footer = match (&logic.message, logic.capturing, logic.conflicts.first()) {
+            (_, true, _) => format!("Press a key for {:?} (Escape to cancel)", logic.selected_action()),
+            (Some(message), false, _) => message.clone(),
+            (None, false, Some((key, first, second))) => format!("{} is bound to both {:?} and {:?}", key, first, second),
+            (None, false, None) => "Enter add   Backspace remove   R reset   Escape save".to_string(),
+        };
+
+        lines.push(footer);
+
+        if self.selected == Some(logic.selected) && lines == self.lines {
+            return;
+        }
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build_selection(logic, self.size, device);
+
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.indices_count = indices_count;
+
+        let scale_factor = self.scale_factor;
+        let ivory = [IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0];
+        let yellow = [YELLOW.0 as f32 / 255.0, YELLOW.1 as f32 / 255.0, YELLOW.2 as f32 / 255.0, 1.0];
+        let grey = [DARKGREY.0 as f32 / 255.0, DARKGREY.1 as f32 / 255.0, DARKGREY.2 as f32 / 255.0, 1.0];
+        let red = [RED.0 as f32 / 255.0, RED.1 as f32 / 255.0, RED.2 as f32 / 255.0, 1.0];
+
+        let mut texts = Vec::<Section>::new();
+
+        texts.push(Section::default()
+            .add_text(Text::new("KEY BINDINGS").with_scale(60.0 * scale_factor).with_color(ivory))
+            .with_screen_position(PhysicalPosition::new(150.0 * scale_factor, 50.0 * scale_factor)));
+
+        let rows = lines[..lines.len() - 1].chunks(2);
+        let (first, count) = Self::visible_rows(logic, self.size.1);
+
+        for ((i, row), action) in rows.enumerate().zip(logic.key_bindings.keys.keys()).skip(first).take(count) {
+            let up = (ROW_TOP + (i - first) as u32 * ROW_HEIGHT) as f32;
+            let color = if i == logic.selected { yellow } else if logic.is_conflicting(*action) { red } else { ivory };
+
+            texts.push(Section::default()
+                .add_text(Text::new(&row[0]).with_scale(32.0 * scale_factor).with_color(color))
+                .with_screen_position(PhysicalPosition::new(170.0 * scale_factor, up * scale_factor)));
+
+            texts.push(Section::default()
+                .add_text(Text::new(&row[1]).with_scale(32.0 * scale_factor).with_color(color))
+                .with_screen_position(PhysicalPosition::new(500.0 * scale_factor, up * scale_factor))
+                .with_layout(
+                    Layout::default()
+                        .line_breaker(BuiltInLineBreaker::AnyCharLineBreaker),
+                ));
+        }
+
+        let footer_color = if logic.capturing { yellow } else if logic.message.is_none() && !logic.conflicts.is_empty() { red } else { grey };
+
+        texts.push(Section::default()
+            .add_text(Text::new(lines.last().unwrap()).with_scale(32.0 * scale_factor).with_color(footer_color))
+            .with_screen_position(PhysicalPosition::new(150.0 * scale_factor, (self.size.1 as f32 - 70.0) * scale_factor)));
+
+        self.brush.queue(device, queue, texts).expect("Failed to draw key bindings text");
+
+        self.selected = Some(logic.selected);
+        self.lines = lines;
+    }
+
+    fn process_resize(&mut self, (width, height): (u32, u32), scale_factor: f32, queue: &Queue) {
+        self.brush.resize_view(width as f32, height as f32, queue);
+
+        self.size = ((width as f32 / scale_factor) as u32, (height as f32 / scale_factor) as u32);
+        self.scale_factor = scale_factor;
+        self.selected = None;
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, self.size.0 as f32, self.size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+
+        queue.write_buffer(&self.projection_view_model_uniform, 0, bytemuck::cast_slice(projection_view_model_ref));
+    }
+
+    fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
+        render_pass.set_pipeline(&self.color.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.draw_indexed(0..self.indices_count as u32, 0, 0..1);
+
+        self.brush.draw(render_pass);
+    }
+}
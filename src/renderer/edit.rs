@@ -1,23 +1,316 @@
 use std::rc::Rc;
-use wgpu::{Queue, RenderPass};
-use crate::logic::edit::EditLogic;
-use crate::renderer::utils::pipeline::{ColorPipeline, TexturePipeline};
+use glam::Mat4;
+use wgpu::{BindGroup, Buffer, Device, Queue, RenderPass, SurfaceConfiguration};
+use wgpu::util::DeviceExt;
+use wgpu_text::glyph_brush::ab_glyph::FontRef;
+use wgpu_text::{BrushBuilder, TextBrush};
+use wgpu_text::glyph_brush::{Section, Text};
+use winit::dpi::PhysicalPosition;
+
+use crate::logic::edit::{Dialog, EditLogic, EditMode, PALETTE_ENTRY};
+use crate::logic::play::level::Tile;
+use crate::logic::scene::Scene;
+use crate::renderer::ColorVertex;
+use crate::renderer::play::level::{draw_level, tile_color};
+use crate::renderer::scene::SceneRenderer;
+use crate::renderer::utils::draw_text_box;
+use crate::renderer::utils::palette::{DARKGREY, IVORY, RED, YELLOW};
+use crate::renderer::utils::pipeline::ColorPipeline;
+use crate::renderer::utils::quad::draw_color_quad;
+
+const ROW_TOP: u32 = 150;
+const ROW_HEIGHT: u32 = 45;
+const DIALOG_SIZE: (u32, u32) = (900, 170);
+
+// Everything the editor draws depends on, compared each frame to skip rebuilding unchanged buffers
+
+#[derive(PartialEq)]
+struct Snapshot {
+    mode: EditMode,
+    lines: Vec<String>,
+    dialog: Option<Dialog>,
+    tiles: Vec<Tile>,
+    grid: ((u32, u32), u32),
+    palette: Vec<(u32, u32)>,
+    tile: usize,
+    selected: usize,
+    hovered_cell: Option<(u32, u32)>,
+    hovered_tile: Option<usize>,
+}
 
 pub struct EditRenderer {
+    brush: TextBrush<FontRef<'static>>,
+    projection_view_model_uniform: Buffer,
+    vertex_buffer: Buffer,
+    index_buffer: Buffer,
+    indices_count: usize,
+    bind_group: BindGroup,
+
+    size: (u32, u32),
+    scale_factor: f32,
+    snapshot: Option<Snapshot>,
+
     color: Rc<ColorPipeline>,
-    texture: Rc<TexturePipeline>,
 }
 
 impl EditRenderer {
-    pub fn new(logic: &EditLogic, color: Rc<ColorPipeline>, texture: Rc<TexturePipeline>) -> Self {
-        return Self {
+    pub fn new(logic: &EditLogic, color: Rc<ColorPipeline>, device: &Device, queue: &Queue, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
+        let font: &[u8] = include_bytes!("fonts/BulletTrace7-rppO.ttf");
+        let brush = BrushBuilder::using_font_bytes(font).unwrap().build(
+            device,
+            config.width,
+            config.height,
+            config.format,
+        );
+
+        let size = ((config.width as f32 / scale_factor) as u32, (config.height as f32 / scale_factor) as u32);
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, size.0 as f32, size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+        let projection_view_model_uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Uniform Buffer"),
+            contents: bytemuck::cast_slice(projection_view_model_ref),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Edit BindGroup"),
+            layout: &color.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: projection_view_model_uniform.as_entire_binding(),
+                },
+            ],
+        });
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build(logic, size, device);
+
+        let mut renderer = Self {
+            brush,
+            projection_view_model_uniform,
+            vertex_buffer,
+            index_buffer,
+            indices_count,
+            bind_group,
+            size,
+            scale_factor,
+            snapshot: None,
             color,
-            texture
         };
+
+        renderer.update(logic, 0.0, device, queue);
+
+        return renderer;
+    }
+
+    fn lines(logic: &EditLogic) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        match logic.mode {
+            EditMode::Setup => {
+                lines.push("LEVEL EDITOR".to_string());
+                lines.extend(logic.setup_rows());
+            }
+            EditMode::Paint => {
+                let name = logic.path.as_ref().map_or("Untitled".to_string(), |path| path.display().to_string());
+                let dirty = if logic.dirty { "*" } else { "" };
+
+                let title = if logic.level.name.is_empty() { String::new() } else { format!(" - {}", logic.level.name) };
+
+                lines.push(format!("{}{}{} ({}x{})", name, dirty, title, logic.level.width, logic.level.height));
+                lines.extend(Tile::ALL.iter().map(|tile| tile.name().to_string()));
+            }
+        }
+
+        return lines;
+    }
+
+    fn dialog_lines(dialog: &Dialog) -> (&'static str, String, &'static str) {
+        return match dialog {
+            Dialog::Save(text) => ("Save level as", format!("{}_", text), "Enter - save   Escape - cancel"),
+            Dialog::Open(text) => ("Open level", format!("{}_", text), "Enter - open   Escape - cancel"),
+            Dialog::Rename(text) => ("Level name", format!("{}_", text), "Enter - rename   Escape - cancel"),
+            Dialog::Discard(_) => ("Unsaved changes", "Discard the changes to this level?".to_string(), "Enter - discard   Escape - keep editing"),
+            Dialog::Error(message) => ("Error", message.clone(), "Enter - OK"),
+        };
+    }
+
+    fn dialog_position(size: (u32, u32)) -> (u32, u32) {
+        return (size.0.saturating_sub(DIALOG_SIZE.0) / 2, size.1.saturating_sub(DIALOG_SIZE.1) / 2);
+    }
+
+    fn build(logic: &EditLogic, size: (u32, u32), device: &Device) -> (Buffer, Buffer, usize) {
+        let mut vertex_data: Vec<ColorVertex> = Vec::new();
+        let mut index_data: Vec<u16> = Vec::new();
+
+        match logic.mode {
+            EditMode::Setup => {
+                draw_text_box(&mut vertex_data, &mut index_data, (150, ROW_TOP - 8 + logic.selected as u32 * ROW_HEIGHT), (700, ROW_HEIGHT - 10), 4, 3);
+            }
+            EditMode::Paint => {
+                // Draw palette
+
+                for (i, &position) in logic.palette.iter().enumerate() {
+                    if logic.tile == i {
+                        draw_text_box(&mut vertex_data, &mut index_data, position, PALETTE_ENTRY, 6, 3);
+                    } else if logic.hovered_tile == Some(i) {
+                        draw_color_quad(&mut vertex_data, &mut index_data, (position.0 - 4, position.1 - 4), (PALETTE_ENTRY.0 + 8, PALETTE_ENTRY.1 + 8), (YELLOW.0, YELLOW.1, YELLOW.2, 255));
+                        draw_color_quad(&mut vertex_data, &mut index_data, position, PALETTE_ENTRY, (DARKGREY.0, DARKGREY.1, DARKGREY.2, 255));
+                    }
+
+                    let color = tile_color(Tile::ALL[i]);
+
+                    draw_color_quad(&mut vertex_data, &mut index_data, (position.0 + 6, position.1 + 6), (PALETTE_ENTRY.1 - 12, PALETTE_ENTRY.1 - 12), (color.0, color.1, color.2, 255));
+                }
+
+                // Draw grid and hover cursor
+
+                let (origin, cell) = logic.grid;
+
+                draw_level(&mut vertex_data, &mut index_data, &logic.level, origin, cell);
+
+                if let Some((x, y)) = logic.hovered_cell {
+                    draw_color_quad(&mut vertex_data, &mut index_data, (origin.0 + x * cell, origin.1 + y * cell), (cell + 1, cell + 1), (YELLOW.0, YELLOW.1, YELLOW.2, 120));
+                }
+            }
+        }
+
+        if logic.dialog.is_some() {
+            draw_text_box(&mut vertex_data, &mut index_data, Self::dialog_position(size), DIALOG_SIZE, 8, 3);
+        }
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Edit VertexBuffer"),
+            contents: bytemuck::cast_slice(&vertex_data),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Edit IndexBuffer"),
+            contents: bytemuck::cast_slice(&index_data),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        return (vertex_buffer, index_buffer, index_data.len());
+    }
+}
+
+impl SceneRenderer for EditRenderer {
+    fn update(&mut self, logic: &dyn Scene, _alpha: f32, device: &Device, queue: &Queue) {
+        let logic = logic.as_any().downcast_ref::<EditLogic>().expect("EditRenderer expects an EditLogic");
+
+        let snapshot = Snapshot {
+            mode: logic.mode,
+            lines: Self::lines(logic),
+            dialog: logic.dialog.clone(),
+            tiles: logic.level.tiles().to_vec(),
+            grid: logic.grid,
+            palette: logic.palette.clone(),
+            tile: logic.tile,
+            selected: logic.selected,
+            hovered_cell: logic.hovered_cell,
+            hovered_tile: logic.hovered_tile,
+        };
+
+        if self.snapshot.as_ref() == Some(&snapshot) {
+            return;
+        }
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build(logic, self.size, device);
+
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.indices_count = indices_count;
+
+        let scale_factor = self.scale_factor;
+        let ivory = [IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0];
+        let yellow = [YELLOW.0 as f32 / 255.0, YELLOW.1 as f32 / 255.0, YELLOW.2 as f32 / 255.0, 1.0];
+        let grey = [DARKGREY.0 as f32 / 255.0, DARKGREY.1 as f32 / 255.0, DARKGREY.2 as f32 / 255.0, 1.0];
+        let red = [RED.0 as f32 / 255.0, RED.1 as f32 / 255.0, RED.2 as f32 / 255.0, 1.0];
+
+        let lines = &snapshot.lines;
+        let rows = logic.setup_rows();
+        let mut texts = Vec::<Section>::new();
+
+        texts.push(Section::default()
+            .add_text(Text::new(&lines[0]).with_scale(48.0 * scale_factor).with_color(ivory))
+            .with_screen_position(PhysicalPosition::new(40.0 * scale_factor, 40.0 * scale_factor)));
+
+        let hint = match logic.mode {
+            EditMode::Setup => {
+                for (i, row) in rows.iter().enumerate() {
+                    texts.push(Section::default()
+                        .add_text(Text::new(row).with_scale(32.0 * scale_factor).with_color(if i == logic.selected { yellow } else { ivory }))
+                        .with_screen_position(PhysicalPosition::new(170.0 * scale_factor, (ROW_TOP + i as u32 * ROW_HEIGHT) as f32 * scale_factor)));
+                }
+
+                "Up/Down select   Left/Right resize   Enter confirm   Esc menu"
+            }
+            EditMode::Paint => {
+                for (i, &(left, up)) in logic.palette.iter().enumerate() {
+                    texts.push(Section::default()
+                        .add_text(Text::new(Tile::ALL[i].name()).with_scale(28.0 * scale_factor).with_color(if logic.tile == i { yellow } else { ivory }))
+                        .with_screen_position(PhysicalPosition::new((left + PALETTE_ENTRY.1 + 4) as f32 * scale_factor, (up + 8) as f32 * scale_factor)));
+                }
+
+                "LMB paint   RMB erase   Wheel tile   F fill   Arrows resize   Backspace undo   Y redo\nN rename   F5 playtest   F6 save   F7 open   Esc menu"
+            }
+        };
+
+        texts.push(Section::default()
+            .add_text(Text::new(hint).with_scale(24.0 * scale_factor).with_color(grey))
+            .with_screen_position(PhysicalPosition::new(40.0 * scale_factor, (self.size.1 as f32 - 70.0) * scale_factor)));
+
+        // Draw dialog
+
+        let dialog = logic.dialog.as_ref().map(Self::dialog_lines);
+
+        if let Some((title, body, footer)) = &dialog {
+            let (left, up) = Self::dialog_position(self.size);
+            let (left, up) = (left as f32 + 20.0, up as f32 + 15.0);
+            let title_color = if matches!(logic.dialog, Some(Dialog::Error(_))) { red } else { yellow };
+
+            texts.push(Section::default()
+                .add_text(Text::new(title).with_scale(36.0 * scale_factor).with_color(title_color))
+                .with_screen_position(PhysicalPosition::new(left * scale_factor, up * scale_factor)));
+
+            texts.push(Section::default()
+                .add_text(Text::new(body).with_scale(26.0 * scale_factor).with_color(ivory))
+                .with_screen_position(PhysicalPosition::new(left * scale_factor, (up + 50.0) * scale_factor))
+                .with_bounds(((DIALOG_SIZE.0 - 40) as f32 * scale_factor, 60.0 * scale_factor)));
+
+            texts.push(Section::default()
+                .add_text(Text::new(footer).with_scale(24.0 * scale_factor).with_color(grey))
+                .with_screen_position(PhysicalPosition::new(left * scale_factor, (up + 115.0) * scale_factor)));
+        }
+
+        self.brush.queue(device, queue, texts).expect("Failed to draw editor text");
+
+        self.snapshot = Some(snapshot);
+    }
+
+    fn process_resize(&mut self, (width, height): (u32, u32), scale_factor: f32, queue: &Queue) {
+        self.brush.resize_view(width as f32, height as f32, queue);
+
+        self.size = ((width as f32 / scale_factor) as u32, (height as f32 / scale_factor) as u32);
+        self.scale_factor = scale_factor;
+        self.snapshot = None;
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, self.size.0 as f32, self.size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+
+        queue.write_buffer(&self.projection_view_model_uniform, 0, bytemuck::cast_slice(projection_view_model_ref));
     }
 
-    pub fn update(&mut self, logic: &EditLogic) {}
+    fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
+        render_pass.set_pipeline(&self.color.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
 
-    pub fn process_resize(&mut self, (width, height): (u32, u32), queue: &Queue) {}
-    pub fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {}
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.draw_indexed(0..self.indices_count as u32, 0, 0..1);
+
+        self.brush.draw(render_pass);
+    }
 }
\ No newline at end of file
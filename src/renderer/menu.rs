@@ -1,19 +1,30 @@
 use std::rc::Rc;
 use glam::Mat4;
-use image::RgbaImage;
-use wgpu::{BindGroup, Buffer, Device, MultisampleState, Queue, RenderPass, SurfaceConfiguration, Texture, TextureView};
+use wgpu::{BindGroup, Buffer, Device, Queue, RenderPass, SurfaceConfiguration};
 use wgpu::util::DeviceExt;
 use wgpu_text::glyph_brush::ab_glyph::FontRef;
 use wgpu_text::{BrushBuilder, TextBrush};
 use wgpu_text::glyph_brush::{BuiltInLineBreaker, Layout, Section, Text};
-use winit::dpi::{LogicalPosition, PhysicalPosition};
+use winit::dpi::PhysicalPosition;
 
 use crate::logic::menu::MenuLogic;
+use crate::logic::scene::Scene;
 use crate::renderer::{ColorVertex, TextureVertex};
+use crate::renderer::scene::SceneRenderer;
 use crate::renderer::utils::draw_text_box;
-use crate::renderer::utils::palette::{BLACK, DARKBLUE, IVORY};
+use crate::renderer::utils::palette::{BLACK, DARKGREY, IVORY, YELLOW};
 use crate::renderer::utils::pipeline::{ColorPipeline, TexturePipeline};
-use crate::renderer::utils::quad::draw_texture_quad;
+use crate::renderer::utils::quad::{draw_color_quad, draw_texture_quad};
+
+// Title and info text above the level grid, measured with the brush so the grid starts below them
+
+struct Header {
+    title: Section<'static>,
+    info: Section<'static>,
+    position: (u32, u32),
+    size: (u32, u32),
+    layout_area: ((u32, u32), (u32, u32)),
+}
 
 struct Background {
     pub bind_group: BindGroup,
@@ -32,62 +43,32 @@ pub struct MenuRenderer {
 
     background: Background,
 
+    size: (u32, u32),
+    scale_factor: f32,
+    layout_area: ((u32, u32), (u32, u32)),
+    hovered: Option<usize>,
+    focused: Option<usize>,
+    page: usize,
+    locked: Vec<bool>,
+    dirty: bool,
+
     color: Rc<ColorPipeline>,
     texture: Rc<TexturePipeline>,
 }
 
 impl MenuRenderer {
-    pub fn new(logic: &MenuLogic, color: Rc<ColorPipeline>, texture: Rc<TexturePipeline>, device: &Device, queue: &Queue, config: &SurfaceConfiguration) -> Self {
+    pub fn new(logic: &MenuLogic, color: Rc<ColorPipeline>, texture: Rc<TexturePipeline>, device: &Device, queue: &Queue, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
         let font: &[u8] = include_bytes!("fonts/BulletTrace7-rppO.ttf");
         let mut brush = BrushBuilder::using_font_bytes(font).unwrap().build(
-            &device,
+            device,
             config.width,
             config.height,
             config.format,
         );
 
-        let text = "SPRINT THE GAME".to_string();
-
-        let mut title = Section::default()
-            .add_text(
-                Text::new(&text)
-                    .with_scale(80.0)
-                    .with_color([IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0]),
-            )
-            .with_layout(
-                Layout::default()
-                    .line_breaker(BuiltInLineBreaker::AnyCharLineBreaker),
-            );
-
-        let bounds = brush.glyph_bounds(title.clone()).unwrap();
-        let title = title.with_screen_position(PhysicalPosition::new(config.width as f32 / 2.0 - bounds.width() / 2.0, config.height as f32 / 4.0 - bounds.height() / 2.0));
-        let height = bounds.height();
-        let up = config.height as f32 / 4.0 - bounds.height() / 2.0;
-
-        let text = "Select Level to Start".to_string();
-
-        let info = Section::default()
-            .add_text(
-                Text::new(&text)
-                    .with_scale(80.0)
-                    .with_color([IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0]),
-            )
-            .with_layout(
-                Layout::default()
-                    .line_breaker(BuiltInLineBreaker::AnyCharLineBreaker),
-            );
-
-        let bounds = brush.glyph_bounds(info.clone()).unwrap();
-        let info = info.with_screen_position(PhysicalPosition::new(config.width as f32 / 2.0 - bounds.width() / 2.0, config.height as f32 / 4.0 - bounds.height() / 2.0 + height));
-        let left = config.width as f32 / 2.0 - bounds.width() / 2.0;
-        let width = bounds.width();
-        let height = height + bounds.height();
-
-        let mut texts = Vec::<Section>::new();
-        texts.push(title);
-        texts.push(info);
+        let size = ((config.width as f32 / scale_factor) as u32, (config.height as f32 / scale_factor) as u32);
 
-        let projection_view_model_data = Mat4::orthographic_rh(0f32, config.width as f32, config.height as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, size.0 as f32, size.1 as f32, 0f32, -1f32, 1f32);
         let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
         let projection_view_model_uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Uniform Buffer"),
@@ -106,54 +87,8 @@ impl MenuRenderer {
             ],
         });
 
-        let mut vertex_data: Vec<ColorVertex> = Vec::new();
-        let mut index_data: Vec<u16> = Vec::new();
-
-        // Draw Main box
-
-        draw_text_box(&mut vertex_data, &mut index_data, (left as u32, up as u32), (width as u32, height as u32), 15, 15);
-
-        // Draw buttons
-
-        let mut text = Vec::<String>::new();
-
-        for level_button in &logic.level_buttons {
-            draw_text_box(&mut vertex_data, &mut index_data, level_button.position, (250, 100), 0, 5);
-
-            let mut t = "Level ".to_owned();
-            t.push_str(level_button.id.to_string().as_str());
-            text.push(t);
-
-            let button = Section::default()
-                .add_text(
-                    Text::new(text.last().unwrap().clone().as_str())
-                        .with_scale(40.0)
-                        .with_color([IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0]),
-                )
-                .with_screen_position(LogicalPosition::new(level_button.position.0 + 25, level_button.position.1 + 30))
-                .with_layout(
-                    Layout::default()
-                        .line_breaker(BuiltInLineBreaker::AnyCharLineBreaker),
-                );
-
-            texts.push(button);
-        }
-
-        let indices_count = index_data.len();
-
-        brush.queue(&device, &queue, texts).expect("Failed to draw main menu text");
-
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Menu VertexBuffer"),
-            contents: bytemuck::cast_slice(&vertex_data),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Menu IndexBuffer"),
-            contents: bytemuck::cast_slice(&index_data),
-            usage: wgpu::BufferUsages::INDEX,
-        });
+        let (vertex_buffer, index_buffer, indices_count) = Self::build(logic, &mut brush, size, scale_factor, device, queue);
+        let layout_area = Self::header(&mut brush, size, scale_factor).layout_area;
 
         let diffuse_bytes = include_bytes!("images/Sprint_Background.png");
         let diffuse_image = image::load_from_memory(diffuse_bytes).unwrap();
@@ -220,24 +155,7 @@ impl MenuRenderer {
             }
         );
 
-        let mut vertex_data: Vec<TextureVertex> = Vec::new();
-        let mut index_data: Vec<u16> = Vec::new();
-
-        draw_texture_quad(&mut vertex_data, &mut index_data, (0, 0), (config.width, config.height));
-
-        let background_indices_count = index_data.len();
-
-        let background_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Menu VertexBuffer"),
-            contents: bytemuck::cast_slice(&vertex_data),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let background_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Menu IndexBuffer"),
-            contents: bytemuck::cast_slice(&index_data),
-            usage: wgpu::BufferUsages::INDEX,
-        });
+        let (background_vertex_buffer, background_index_buffer, background_indices_count) = Self::build_background(size, device);
 
         let background = Background {
             bind_group: diffuse_bind_group,
@@ -254,21 +172,262 @@ impl MenuRenderer {
             indices_count,
             bind_group,
             background,
+            size,
+            scale_factor,
+            layout_area,
+            hovered: logic.hovered,
+            focused: logic.focused,
+            page: logic.page,
+            locked: Vec::new(),
+            dirty: true,
             color,
             texture,
         };
     }
 
-    pub fn update(&mut self, logic: &MenuLogic) {}
-    pub fn process_resize(&mut self, (width, height): (u32, u32), queue: &Queue) {
-        self.brush.resize_view(width as f32, height as f32, &queue);
+    fn header(brush: &mut TextBrush<FontRef<'static>>, (width, height): (u32, u32), scale_factor: f32) -> Header {
+        let ivory = [IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0];
+
+        let title = Section::default()
+            .add_text(
+                Text::new("SPRINT THE GAME")
+                    .with_scale(80.0 * scale_factor)
+                    .with_color(ivory),
+            )
+            .with_layout(
+                Layout::default()
+                    .line_breaker(BuiltInLineBreaker::AnyCharLineBreaker),
+            );
+
+        let bounds = brush.glyph_bounds(title.clone()).unwrap();
+        let (title_width, title_height) = (bounds.width() / scale_factor, bounds.height() / scale_factor);
+        let up = height as f32 / 4.0 - title_height / 2.0;
+        let title = title.with_screen_position(PhysicalPosition::new((width as f32 / 2.0 - title_width / 2.0) * scale_factor, up * scale_factor));
+
+        let info = Section::default()
+            .add_text(
+                Text::new("Select Level to Start")
+                    .with_scale(80.0 * scale_factor)
+                    .with_color(ivory),
+            )
+            .with_layout(
+                Layout::default()
+                    .line_breaker(BuiltInLineBreaker::AnyCharLineBreaker),
+            );
+
+        let bounds = brush.glyph_bounds(info.clone()).unwrap();
+        let (info_width, info_height) = (bounds.width() / scale_factor, bounds.height() / scale_factor);
+        let left = width as f32 / 2.0 - info_width / 2.0;
+        let info = info.with_screen_position(PhysicalPosition::new(left * scale_factor, (up + title_height) * scale_factor));
+
+        let grid_up = (up + title_height + info_height) as u32 + 15 + 2 + 15 + 2 + 40;
+
+        return Header {
+            title,
+            info,
+            position: (left as u32, up as u32),
+            size: (info_width as u32, (title_height + info_height) as u32),
+            layout_area: ((40, grid_up), (width.saturating_sub(80), height.saturating_sub(grid_up + 80))),
+        };
+    }
+
+    fn build(logic: &MenuLogic, brush: &mut TextBrush<FontRef<'static>>, (width, height): (u32, u32), scale_factor: f32, device: &Device, queue: &Queue) -> (Buffer, Buffer, usize) {
+        let ivory = [IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0];
+
+        let header = Self::header(brush, (width, height), scale_factor);
+
+        let mut texts = Vec::<Section>::new();
+        texts.push(header.title);
+        texts.push(header.info);
+
+        let mut vertex_data: Vec<ColorVertex> = Vec::new();
+        let mut index_data: Vec<u16> = Vec::new();
+
+        // Draw Main box
+
+        draw_text_box(&mut vertex_data, &mut index_data, header.position, header.size, 15, 15);
+
+        // Draw buttons
+
+        let text = logic.level_buttons.iter().map(|level_button| format!("Level {}", level_button.id)).collect::<Vec<String>>();
+
+        for (i, (level_button, t)) in logic.level_buttons.iter().zip(&text).enumerate() {
+            if !logic.is_visible(i) {
+                continue;
+            }
+
+            if logic.hovered == Some(i) {
+                draw_color_quad(&mut vertex_data, &mut index_data, (level_button.position.0.saturating_sub(13), level_button.position.1.saturating_sub(13)), (level_button.size.0 + 26, level_button.size.1 + 26), (YELLOW.0, YELLOW.1, YELLOW.2, 255));
+            }
+
+            if logic.focused == Some(i) {
+                draw_text_box(&mut vertex_data, &mut index_data, level_button.position, level_button.size, 8, 3);
+            }
+
+            draw_text_box(&mut vertex_data, &mut index_data, level_button.position, level_button.size, 0, 5);
+
+            if level_button.locked {
+                let (x, y) = level_button.position;
+                let (width, height) = level_button.size;
+                let black = (BLACK.0, BLACK.1, BLACK.2, 255);
+
+                draw_color_quad(&mut vertex_data, &mut index_data, (x, y), (width, height), (DARKGREY.0, DARKGREY.1, DARKGREY.2, 255));
+
+                // Draw padlock
+
+                let (x, y) = (x + width - 60, y + height / 2 - 5);
+
+                draw_color_quad(&mut vertex_data, &mut index_data, (x, y), (30, 24), black);
+                draw_color_quad(&mut vertex_data, &mut index_data, (x + 4, y - 14), (5, 14), black);
+                draw_color_quad(&mut vertex_data, &mut index_data, (x + 21, y - 14), (5, 14), black);
+                draw_color_quad(&mut vertex_data, &mut index_data, (x + 4, y - 18), (22, 5), black);
+            }
+
+            let text_color = if level_button.locked {
+                [BLACK.0 as f32 / 255.0, BLACK.1 as f32 / 255.0, BLACK.2 as f32 / 255.0, 1.0]
+            } else if logic.hovered == Some(i) {
+                [YELLOW.0 as f32 / 255.0, YELLOW.1 as f32 / 255.0, YELLOW.2 as f32 / 255.0, 1.0]
+            } else {
+                ivory
+            };
+
+            let button = Section::default()
+                .add_text(
+                    Text::new(t)
+                        .with_scale(40.0 * scale_factor)
+                        .with_color(text_color),
+                )
+                .with_screen_position(PhysicalPosition::new((level_button.position.0 + 25) as f32 * scale_factor, (level_button.position.1 + 30) as f32 * scale_factor))
+                .with_layout(
+                    Layout::default()
+                        .line_breaker(BuiltInLineBreaker::AnyCharLineBreaker),
+                );
+
+            texts.push(button);
+        }
+
+        let hint = Section::default()
+            .add_text(
+                Text::new("F1 - Key bindings   F2 - Settings   F3 - Editor")
+                    .with_scale(30.0 * scale_factor)
+                    .with_color(ivory),
+            );
+
+        let bounds = brush.glyph_bounds(hint.clone()).unwrap();
+        let hint = hint.with_screen_position(PhysicalPosition::new(width as f32 * scale_factor - bounds.width() - 25.0 * scale_factor, height as f32 * scale_factor - bounds.height() - 25.0 * scale_factor));
+
+        texts.push(hint);
+
+        let page_text = format!("< Page {}/{} >", logic.page + 1, logic.page_count);
+
+        if logic.page_count > 1 {
+            let page = Section::default()
+                .add_text(
+                    Text::new(&page_text)
+                        .with_scale(30.0 * scale_factor)
+                        .with_color(ivory),
+                );
+
+            let bounds = brush.glyph_bounds(page.clone()).unwrap();
+            let page = page.with_screen_position(PhysicalPosition::new(width as f32 * scale_factor / 2.0 - bounds.width() / 2.0, height as f32 * scale_factor - bounds.height() - 25.0 * scale_factor));
+
+            texts.push(page);
+        }
+
+        let indices_count = index_data.len();
+
+        brush.queue(device, queue, texts).expect("Failed to draw main menu text");
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Menu VertexBuffer"),
+            contents: bytemuck::cast_slice(&vertex_data),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Menu IndexBuffer"),
+            contents: bytemuck::cast_slice(&index_data),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        return (vertex_buffer, index_buffer, indices_count);
+    }
+
+    fn build_background((width, height): (u32, u32), device: &Device) -> (Buffer, Buffer, usize) {
+        let mut vertex_data: Vec<TextureVertex> = Vec::new();
+        let mut index_data: Vec<u16> = Vec::new();
+
+        draw_texture_quad(&mut vertex_data, &mut index_data, (0, 0), (width, height));
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Menu VertexBuffer"),
+            contents: bytemuck::cast_slice(&vertex_data),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Menu IndexBuffer"),
+            contents: bytemuck::cast_slice(&index_data),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        return (vertex_buffer, index_buffer, index_data.len());
+    }
+}
+
+impl SceneRenderer for MenuRenderer {
+    fn update(&mut self, logic: &dyn Scene, _alpha: f32, device: &Device, queue: &Queue) {
+        let logic = logic.as_any().downcast_ref::<MenuLogic>().expect("MenuRenderer expects a MenuLogic");
+
+        let locked = logic.level_buttons.iter().map(|level_button| level_button.locked).collect::<Vec<bool>>();
+
+        if !self.dirty && self.hovered == logic.hovered && self.focused == logic.focused && self.page == logic.page && self.locked == locked {
+            return;
+        }
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build(logic, &mut self.brush, self.size, self.scale_factor, device, queue);
 
-        let projection_view_model_data = Mat4::orthographic_rh(0f32, width as f32, height as f32, 0f32, -1f32, 1f32);
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.indices_count = indices_count;
+
+        if self.dirty {
+            let (vertex_buffer, index_buffer, indices_count) = Self::build_background(self.size, device);
+
+            self.background.vertex_buffer = vertex_buffer;
+            self.background.index_buffer = index_buffer;
+            self.background.indices_count = indices_count;
+        }
+
+        self.hovered = logic.hovered;
+        self.focused = logic.focused;
+        self.page = logic.page;
+        self.locked = locked;
+        self.dirty = false;
+    }
+
+    fn process_resize(&mut self, (width, height): (u32, u32), scale_factor: f32, queue: &Queue) {
+        self.brush.resize_view(width as f32, height as f32, queue);
+
+        self.size = ((width as f32 / scale_factor) as u32, (height as f32 / scale_factor) as u32);
+        self.scale_factor = scale_factor;
+        self.layout_area = Self::header(&mut self.brush, self.size, scale_factor).layout_area;
+        self.dirty = true;
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, self.size.0 as f32, self.size.1 as f32, 0f32, -1f32, 1f32);
         let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
 
         queue.write_buffer(&self.projection_view_model_uniform, 0, bytemuck::cast_slice(projection_view_model_ref));
     }
-    pub fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
+
+    fn layout(&self, logic: &mut dyn Scene) {
+        let logic = logic.as_any_mut().downcast_mut::<MenuLogic>().expect("MenuRenderer expects a MenuLogic");
+        let (position, size) = self.layout_area;
+
+        logic.layout_buttons(position, size);
+    }
+
+    fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
         render_pass.set_pipeline(&self.texture.pipeline);
         render_pass.set_bind_group(0, &self.background.bind_group, &[]);
 
@@ -0,0 +1,206 @@
+use std::rc::Rc;
+use glam::Mat4;
+use wgpu::{BindGroup, Buffer, Device, Queue, RenderPass, SurfaceConfiguration};
+use wgpu::util::DeviceExt;
+use wgpu_text::glyph_brush::ab_glyph::FontRef;
+use wgpu_text::{BrushBuilder, TextBrush};
+use wgpu_text::glyph_brush::{Section, Text};
+use winit::dpi::PhysicalPosition;
+
+use crate::logic::panel::ButtonPanel;
+use crate::renderer::ColorVertex;
+use crate::renderer::utils::draw_text_box;
+use crate::renderer::utils::palette::{BLACK, IVORY, YELLOW};
+use crate::renderer::utils::pipeline::ColorPipeline;
+use crate::renderer::utils::quad::draw_color_quad;
+
+// Everything an overlay panel draws depends on, compared each frame to skip rebuilding unchanged buffers
+
+#[derive(PartialEq)]
+struct Snapshot {
+    lines: Vec<String>,
+    position: (u32, u32),
+    size: (u32, u32),
+    labels: Vec<&'static str>,
+    buttons: Vec<((u32, u32), (u32, u32))>,
+    focused: usize,
+    hovered: Option<usize>,
+}
+
+// Draws a ButtonPanel over the dimmed scene below it, the first line is the title
+
+pub struct PanelRenderer {
+    brush: TextBrush<FontRef<'static>>,
+    projection_view_model_uniform: Buffer,
+    vertex_buffer: Buffer,
+    index_buffer: Buffer,
+    indices_count: usize,
+    bind_group: BindGroup,
+
+    size: (u32, u32),
+    scale_factor: f32,
+    snapshot: Option<Snapshot>,
+
+    color: Rc<ColorPipeline>,
+}
+
+impl PanelRenderer {
+    pub fn new(color: Rc<ColorPipeline>, device: &Device, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
+        let font: &[u8] = include_bytes!("fonts/BulletTrace7-rppO.ttf");
+        let brush = BrushBuilder::using_font_bytes(font).unwrap().build(
+            device,
+            config.width,
+            config.height,
+            config.format,
+        );
+
+        let size = ((config.width as f32 / scale_factor) as u32, (config.height as f32 / scale_factor) as u32);
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, size.0 as f32, size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+        let projection_view_model_uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Uniform Buffer"),
+            contents: bytemuck::cast_slice(projection_view_model_ref),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Panel BindGroup"),
+            layout: &color.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: projection_view_model_uniform.as_entire_binding(),
+                },
+            ],
+        });
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build(None, size, device);
+
+        return Self {
+            brush,
+            projection_view_model_uniform,
+            vertex_buffer,
+            index_buffer,
+            indices_count,
+            bind_group,
+            size,
+            scale_factor,
+            snapshot: None,
+            color,
+        };
+    }
+
+    fn build(snapshot: Option<&Snapshot>, (width, height): (u32, u32), device: &Device) -> (Buffer, Buffer, usize) {
+        let mut vertex_data: Vec<ColorVertex> = Vec::new();
+        let mut index_data: Vec<u16> = Vec::new();
+
+        // Dim the scene below
+
+        draw_color_quad(&mut vertex_data, &mut index_data, (0, 0), (width, height), (BLACK.0, BLACK.1, BLACK.2, 160));
+
+        // Draw panel and buttons
+
+        if let Some(snapshot) = snapshot {
+            draw_text_box(&mut vertex_data, &mut index_data, snapshot.position, snapshot.size, 15, 15);
+
+            for (i, (position, size)) in snapshot.buttons.iter().enumerate() {
+                if snapshot.hovered == Some(i) {
+                    draw_color_quad(&mut vertex_data, &mut index_data, (position.0.saturating_sub(13), position.1.saturating_sub(13)), (size.0 + 26, size.1 + 26), (YELLOW.0, YELLOW.1, YELLOW.2, 255));
+                }
+
+                if snapshot.focused == i {
+                    draw_text_box(&mut vertex_data, &mut index_data, *position, *size, 8, 3);
+                }
+
+                draw_text_box(&mut vertex_data, &mut index_data, *position, *size, 0, 5);
+            }
+        }
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Panel VertexBuffer"),
+            contents: bytemuck::cast_slice(&vertex_data),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Panel IndexBuffer"),
+            contents: bytemuck::cast_slice(&index_data),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        return (vertex_buffer, index_buffer, index_data.len());
+    }
+
+    pub fn update<T>(&mut self, panel: &ButtonPanel<T>, lines: Vec<String>, device: &Device, queue: &Queue) {
+        let snapshot = Snapshot {
+            lines,
+            position: panel.position,
+            size: panel.size,
+            labels: panel.buttons.iter().map(|button| button.label).collect(),
+            buttons: panel.buttons.iter().map(|button| (button.position, button.size)).collect(),
+            focused: panel.focused,
+            hovered: panel.hovered,
+        };
+
+        if self.snapshot.as_ref() == Some(&snapshot) {
+            return;
+        }
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build(Some(&snapshot), self.size, device);
+
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.indices_count = indices_count;
+
+        let scale_factor = self.scale_factor;
+        let ivory = [IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0];
+        let yellow = [YELLOW.0 as f32 / 255.0, YELLOW.1 as f32 / 255.0, YELLOW.2 as f32 / 255.0, 1.0];
+
+        let (left, up) = snapshot.position;
+
+        let mut texts = Vec::<Section>::new();
+
+        for (i, line) in snapshot.lines.iter().enumerate() {
+            let (scale, offset) = if i == 0 { (60.0, 0.0) } else { (36.0, 30.0 + 45.0 * i as f32) };
+
+            texts.push(Section::default()
+                .add_text(Text::new(line).with_scale(scale * scale_factor).with_color(if i == 0 { yellow } else { ivory }))
+                .with_screen_position(PhysicalPosition::new((left + 40) as f32 * scale_factor, (up as f32 + 20.0 + offset) * scale_factor)));
+        }
+
+        for (i, (label, (position, _))) in snapshot.labels.iter().zip(&snapshot.buttons).enumerate() {
+            texts.push(Section::default()
+                .add_text(Text::new(label).with_scale(32.0 * scale_factor).with_color(if snapshot.hovered == Some(i) { yellow } else { ivory }))
+                .with_screen_position(PhysicalPosition::new((position.0 + 20) as f32 * scale_factor, (position.1 + 20) as f32 * scale_factor)));
+        }
+
+        self.brush.queue(device, queue, texts).expect("Failed to draw panel text");
+
+        self.snapshot = Some(snapshot);
+    }
+
+    pub fn process_resize(&mut self, (width, height): (u32, u32), scale_factor: f32, queue: &Queue) {
+        self.brush.resize_view(width as f32, height as f32, queue);
+
+        self.size = ((width as f32 / scale_factor) as u32, (height as f32 / scale_factor) as u32);
+        self.scale_factor = scale_factor;
+        self.snapshot = None;
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, self.size.0 as f32, self.size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+
+        queue.write_buffer(&self.projection_view_model_uniform, 0, bytemuck::cast_slice(projection_view_model_ref));
+    }
+
+    pub fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
+        render_pass.set_pipeline(&self.color.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.draw_indexed(0..self.indices_count as u32, 0, 0..1);
+
+        self.brush.draw(render_pass);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use std::rc::Rc;
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+
+use crate::logic::pause::PauseLogic;
+use crate::logic::scene::Scene;
+use crate::renderer::panel::PanelRenderer;
+use crate::renderer::scene::SceneRenderer;
+use crate::renderer::utils::pipeline::ColorPipeline;
+
+pub struct PauseRenderer {
+    panel: PanelRenderer,
+}
+
+impl PauseRenderer {
+    pub fn new(logic: &PauseLogic, color: Rc<ColorPipeline>, device: &Device, queue: &Queue, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
+        let mut renderer = Self {
+            panel: PanelRenderer::new(color, device, config, scale_factor),
+        };
+
+        renderer.update(logic, 0.0, device, queue);
+
+        return renderer;
+    }
+}
+
+impl SceneRenderer for PauseRenderer {
+    fn update(&mut self, logic: &dyn Scene, _alpha: f32, device: &Device, queue: &Queue) {
+        let logic = logic.as_any().downcast_ref::<PauseLogic>().expect("PauseRenderer expects a PauseLogic");
+
+        self.panel.update(&logic.panel, vec!["PAUSED".to_string()], device, queue);
+    }
+
+    fn process_resize(&mut self, size: (u32, u32), scale_factor: f32, queue: &Queue) {
+        self.panel.process_resize(size, scale_factor, queue);
+    }
+
+    fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
+        self.panel.render(render_pass);
+    }
+}
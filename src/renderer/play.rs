@@ -1,27 +1,355 @@
+use std::f32::consts::PI;
 use std::rc::Rc;
-use wgpu::{Adapter, BindGroup, Buffer, Device, Face, Queue, RenderPass, RenderPipeline, ShaderModule, Surface, SurfaceConfiguration};
+use glam::Mat4;
+use wgpu::{BindGroup, Buffer, Device, Queue, RenderPass, SurfaceConfiguration};
 use wgpu::util::DeviceExt;
+use wgpu_text::glyph_brush::ab_glyph::FontRef;
+use wgpu_text::{BrushBuilder, TextBrush};
+use wgpu_text::glyph_brush::{Section, Text};
+use winit::dpi::PhysicalPosition;
+
 use crate::logic::play::PlayLogic;
-use crate::renderer::utils::pipeline::{ColorPipeline, TexturePipeline};
+use crate::logic::play::level::{Grid, Level};
+use crate::logic::scene::Scene;
+use crate::renderer::ColorVertex;
+use crate::renderer::play::level::{draw_hex_level, draw_level, draw_runner, hex_center};
+use crate::renderer::scene::SceneRenderer;
+use crate::renderer::utils::palette::{BLUE, DARKGREY, IVORY};
+use crate::renderer::utils::quad::draw_color_quad;
+use crate::renderer::utils::pipeline::ColorPipeline;
+use crate::settings::{Settings, SlideEasing};
+
+pub mod level;
+
+const LEVEL_MARGIN: u32 = 40;
+const MAX_CELL: u32 = 48;
+
+// Seconds per cell of a slide and for the landing squash, both at animation speed 1
+
+const SLIDE_TIME: f32 = 0.045;
+const SQUASH_TIME: f32 = 0.12;
+const SQUASH: f32 = 0.3;
+
+// Where the runner is drawn in pixels and how much it is stretched along each axis
+
+type Pose = ((f32, f32), (f32, f32));
+
+// Where the level sits in the window, square cells are whole pixels wide and hex cells are sized by their corner radius
+
+#[derive(Clone, Copy)]
+enum Layout {
+    Square { origin: (u32, u32), cell: u32 },
+    Hex { origin: (f32, f32), radius: f32 },
+}
+
+impl Layout {
+    // Fits the level into the window with a margin around it, centered
 
-mod level;
+    fn fit(level: &Level, size: (u32, u32)) -> Self {
+        let area = (size.0.saturating_sub(2 * LEVEL_MARGIN), size.1.saturating_sub(2 * LEVEL_MARGIN));
+
+        return match level.grid {
+            Grid::Square => {
+                let cell = (area.0 / level.width).min(area.1 / level.height).clamp(4, MAX_CELL);
+
+                Layout::Square {
+                    origin: (
+                        LEVEL_MARGIN + area.0.saturating_sub(cell * level.width) / 2,
+                        LEVEL_MARGIN + area.1.saturating_sub(cell * level.height) / 2,
+                    ),
+                    cell,
+                }
+            }
+            Grid::Hex => {
+                // Columns overlap by half a hexagon and odd columns reach half a cell further down
+
+                let columns = 1.5 * level.width as f32 + 0.5;
+                let rows = level.height as f32 + if level.width > 1 { 0.5 } else { 0.0 };
+                let radius = (area.0 as f32 / columns).min(area.1 as f32 / (rows * 3f32.sqrt())).clamp(3.0, MAX_CELL as f32 / 2.0);
+
+                Layout::Hex {
+                    origin: (
+                        LEVEL_MARGIN as f32 + (area.0 as f32 - radius * columns).max(0.0) / 2.0,
+                        LEVEL_MARGIN as f32 + (area.1 as f32 - radius * rows * 3f32.sqrt()).max(0.0) / 2.0,
+                    ),
+                    radius,
+                }
+            }
+        };
+    }
+
+    fn center(&self, (x, y): (u32, u32)) -> (f32, f32) {
+        return match *self {
+            Layout::Square { origin, cell } => (
+                origin.0 as f32 + (x as f32 + 0.5) * cell as f32 + 0.5,
+                origin.1 as f32 + (y as f32 + 0.5) * cell as f32 + 0.5,
+            ),
+            Layout::Hex { origin, radius } => hex_center(origin, radius, (x, y)),
+        };
+    }
+
+    fn runner_size(&self) -> f32 {
+        return match *self {
+            Layout::Square { cell, .. } => (cell - 1).saturating_sub(2 * (cell / 4)) as f32,
+            Layout::Hex { radius, .. } => radius.round(),
+        };
+    }
+}
+
+fn lerp(from: (f32, f32), to: (f32, f32), t: f32) -> (f32, f32) {
+    return (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t);
+}
+
+fn ease(easing: SlideEasing, t: f32) -> f32 {
+    return match easing {
+        SlideEasing::Accelerate => t * t,
+        SlideEasing::Constant => t,
+        SlideEasing::Bounce => {
+            let (n, d) = (7.5625, 2.75);
+
+            if t < 1.0 / d {
+                n * t * t
+            } else if t < 2.0 / d {
+                n * (t - 1.5 / d).powi(2) + 0.75
+            } else if t < 2.5 / d {
+                n * (t - 2.25 / d).powi(2) + 0.9375
+            } else {
+                n * (t - 2.625 / d).powi(2) + 0.984375
+            }
+        }
+    };
+}
 
 pub struct PlayRenderer {
+    brush: TextBrush<FontRef<'static>>,
+    projection_view_model_uniform: Buffer,
+    vertex_buffer: Buffer,
+    index_buffer: Buffer,
+    indices_count: usize,
+    bind_group: BindGroup,
+
+    size: (u32, u32),
+    scale_factor: f32,
+    revision: Option<u32>,
+    runner: Option<Pose>,
+
+    animations: bool,
+    animation_speed: f32,
+    slide_easing: SlideEasing,
+
     color: Rc<ColorPipeline>,
-    texture: Rc<TexturePipeline>,
 }
 
 impl PlayRenderer {
-    pub fn new(logic: &PlayLogic, color: Rc<ColorPipeline>, texture: Rc<TexturePipeline>, device: &Device, surface: &Surface, adapter: &Adapter, config: &SurfaceConfiguration) -> Self {
-        return Self {
+    pub fn new(logic: &PlayLogic, color: Rc<ColorPipeline>, device: &Device, queue: &Queue, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
+        let font: &[u8] = include_bytes!("fonts/BulletTrace7-rppO.ttf");
+        let brush = BrushBuilder::using_font_bytes(font).unwrap().build(
+            device,
+            config.width,
+            config.height,
+            config.format,
+        );
+
+        let size = ((config.width as f32 / scale_factor) as u32, (config.height as f32 / scale_factor) as u32);
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, size.0 as f32, size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+        let projection_view_model_uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Uniform Buffer"),
+            contents: bytemuck::cast_slice(projection_view_model_ref),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Play BindGroup"),
+            layout: &color.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: projection_view_model_uniform.as_entire_binding(),
+                },
+            ],
+        });
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build(None, None, false, size, device);
+
+        let mut renderer = Self {
+            brush,
+            projection_view_model_uniform,
+            vertex_buffer,
+            index_buffer,
+            indices_count,
+            bind_group,
+            size,
+            scale_factor,
+            revision: None,
+            runner: None,
+            animations: true,
+            animation_speed: 1.0,
+            slide_easing: SlideEasing::Accelerate,
             color,
-            texture
         };
+
+        renderer.update(logic, 0.0, device, queue);
+
+        return renderer;
     }
 
-    pub fn update(&mut self, logic: &PlayLogic) {}
+    fn build(level: Option<&Level>, runner: Option<Pose>, rewinding: bool, size: (u32, u32), device: &Device) -> (Buffer, Buffer, usize) {
+        let mut vertex_data: Vec<ColorVertex> = Vec::new();
+        let mut index_data: Vec<u16> = Vec::new();
+
+        if let Some(level) = level {
+            let layout = Layout::fit(level, size);
+
+            match layout {
+                Layout::Square { origin, cell } => draw_level(&mut vertex_data, &mut index_data, level, origin, cell),
+                Layout::Hex { origin, radius } => draw_hex_level(&mut vertex_data, &mut index_data, level, origin, radius),
+            }
+
+            // While rewinding the level is washed over in blue, the runner stays on top of it
+
+            if rewinding {
+                draw_color_quad(&mut vertex_data, &mut index_data, (0, 0), size, (BLUE.0, BLUE.1, BLUE.2, 90));
+            }
 
-    pub fn process_resize(&mut self, (width, height): (u32, u32), queue: &Queue) {}
+            if let Some((center, scale)) = runner {
+                draw_runner(&mut vertex_data, &mut index_data, center, scale, layout.runner_size());
+            }
+        }
 
-    pub fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {}
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Play VertexBuffer"),
+            contents: bytemuck::cast_slice(&vertex_data),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Play IndexBuffer"),
+            contents: bytemuck::cast_slice(&index_data),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        return (vertex_buffer, index_buffer, index_data.len());
+    }
+
+    // Eases the runner along the latest slide and squashes it against whatever stopped it
+
+    fn pose(&self, logic: &PlayLogic, alpha: f32) -> Option<Pose> {
+        let runner = logic.runner?;
+        let layout = Layout::fit(logic.level.as_ref()?, self.size);
+
+        if let (true, Some(slide)) = (self.animations, logic.slide) {
+            let age = slide.previous_age + (slide.age - slide.previous_age) * alpha;
+            let cells = slide.from.0.abs_diff(slide.to.0).max(slide.from.1.abs_diff(slide.to.1));
+            let duration = cells as f32 * SLIDE_TIME / self.animation_speed;
+            let squash_time = SQUASH_TIME / self.animation_speed;
+
+            let from = layout.center(slide.from);
+            let to = layout.center(slide.to);
+
+            if age < duration {
+                return Some((lerp(from, to, ease(self.slide_easing, age / duration)), (1.0, 1.0)));
+            }
+
+            if runner == slide.to && age < duration + squash_time {
+                let amount = SQUASH * (PI * (age - duration) / squash_time).sin();
+                let scale = if (to.0 - from.0).abs() > (to.1 - from.1).abs() { (1.0 - amount, 1.0 + amount) } else { (1.0 + amount, 1.0 - amount) };
+
+                return Some((to, scale));
+            }
+        }
+
+        // Otherwise the runner is blended from where it was before the latest tick to where it is now
+
+        let previous = logic.previous_runner.unwrap_or(runner);
+
+        return Some((lerp(layout.center(previous), layout.center(runner), alpha), (1.0, 1.0)));
+    }
+}
+
+impl SceneRenderer for PlayRenderer {
+    fn update(&mut self, logic: &dyn Scene, alpha: f32, device: &Device, queue: &Queue) {
+        let logic = logic.as_any().downcast_ref::<PlayLogic>().expect("PlayRenderer expects a PlayLogic");
+
+        let runner = self.pose(logic, alpha);
+
+        if self.revision == Some(logic.revision()) && self.runner == runner {
+            return;
+        }
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build(logic.level.as_ref(), runner, logic.rewinding, self.size, device);
+
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.indices_count = indices_count;
+        self.runner = runner;
+
+        if self.revision == Some(logic.revision()) {
+            return;
+        }
+
+        let scale_factor = self.scale_factor;
+        let grey = [DARKGREY.0 as f32 / 255.0, DARKGREY.1 as f32 / 255.0, DARKGREY.2 as f32 / 255.0, 1.0];
+        let ivory = [IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0];
+
+        let mut texts = Vec::<Section>::new();
+
+        if logic.playtest {
+            texts.push(Section::default()
+                .add_text(Text::new("Playtest   Esc - back").with_scale(24.0 * scale_factor).with_color(grey))
+                .with_screen_position(PhysicalPosition::new(LEVEL_MARGIN as f32 * scale_factor, 8.0 * scale_factor)));
+        }
+
+        if logic.rewinding {
+            texts.push(Section::default()
+                .add_text(Text::new("<< Rewinding").with_scale(24.0 * scale_factor).with_color(ivory))
+                .with_screen_position(PhysicalPosition::new(self.size.0.saturating_sub(LEVEL_MARGIN + 180) as f32 * scale_factor, 8.0 * scale_factor)));
+        }
+
+        let status = match &logic.level {
+            Some(level) => format!("{}   Moves: {}", level.name, logic.moves),
+            None => String::new(),
+        };
+
+        texts.push(Section::default()
+            .add_text(Text::new(&status).with_scale(24.0 * scale_factor).with_color(ivory))
+            .with_screen_position(PhysicalPosition::new(LEVEL_MARGIN as f32 * scale_factor, self.size.1.saturating_sub(32) as f32 * scale_factor)));
+
+        self.brush.queue(device, queue, texts).expect("Failed to draw play text");
+
+        self.revision = Some(logic.revision());
+    }
+
+    fn configure(&mut self, settings: &Settings) {
+        self.animations = settings.animations;
+        self.animation_speed = settings.animation_speed;
+        self.slide_easing = settings.slide_easing;
+    }
+
+    fn process_resize(&mut self, (width, height): (u32, u32), scale_factor: f32, queue: &Queue) {
+        self.brush.resize_view(width as f32, height as f32, queue);
+
+        self.size = ((width as f32 / scale_factor) as u32, (height as f32 / scale_factor) as u32);
+        self.scale_factor = scale_factor;
+        self.revision = None;
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, self.size.0 as f32, self.size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+
+        queue.write_buffer(&self.projection_view_model_uniform, 0, bytemuck::cast_slice(projection_view_model_ref));
+    }
+
+    fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
+        if self.indices_count > 0 {
+            render_pass.set_pipeline(&self.color.pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            render_pass.draw_indexed(0..self.indices_count as u32, 0, 0..1);
+        }
+
+        self.brush.draw(render_pass);
+    }
 }
\ No newline at end of file
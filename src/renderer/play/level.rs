@@ -1,3 +1,88 @@
-pub struct LevelRenderer {
+use std::f32::consts::PI;
 
+use crate::logic::play::level::{Level, Tile};
+use crate::renderer::ColorVertex;
+use crate::renderer::utils::palette::{DARKBLUE, DARKGREY, GREEN, IVORY, RED, VOLKSWAGEN_TAUPE, YELLOW};
+use crate::renderer::utils::quad::draw_color_quad;
+
+pub fn tile_color(tile: Tile) -> (u8, u8, u8) {
+    return match tile {
+        Tile::Empty => DARKBLUE,
+        Tile::Wall => VOLKSWAGEN_TAUPE,
+        Tile::Start => GREEN,
+        Tile::Goal => YELLOW,
+        Tile::Spike => RED,
+    };
+}
+
+// Draws every tile as a quad, the grey background shows through the one pixel gaps as grid lines
+
+pub fn draw_level(vertices: &mut Vec<ColorVertex>, indices: &mut Vec<u16>, level: &Level, (left, up): (u32, u32), cell: u32) {
+    draw_color_quad(vertices, indices, (left, up), (level.width * cell + 1, level.height * cell + 1), (DARKGREY.0, DARKGREY.1, DARKGREY.2, 255));
+
+    for y in 0..level.height {
+        for x in 0..level.width {
+            let color = tile_color(level.get((x, y)));
+
+            draw_color_quad(vertices, indices, (left + x * cell + 1, up + y * cell + 1), (cell - 1, cell - 1), (color.0, color.1, color.2, 255));
+        }
+    }
+}
+
+// Hex cells are flat topped and measured by their corner radius, odd columns sit half a cell lower
+
+pub fn hex_center((left, up): (f32, f32), radius: f32, (x, y): (u32, u32)) -> (f32, f32) {
+    let half_height = radius * 3f32.sqrt() / 2.0;
+
+    return (left + radius + 1.5 * radius * x as f32, up + half_height * (1.0 + 2.0 * y as f32 + (x % 2) as f32));
+}
+
+fn draw_color_hexagon(vertices: &mut Vec<ColorVertex>, indices: &mut Vec<u16>, (x, y): (f32, f32), radius: f32, color: (u8, u8, u8, u8)) {
+    let offset = vertices.len() as u16;
+
+    vertices.push(ColorVertex {
+        position: [x, y],
+        color: [color.0, color.1, color.2, color.3],
+    });
+
+    for corner in 0..6 {
+        let angle = corner as f32 * PI / 3.0;
+
+        vertices.push(ColorVertex {
+            position: [x + radius * angle.cos(), y + radius * angle.sin()],
+            color: [color.0, color.1, color.2, color.3],
+        });
+    }
+
+    for corner in 0..6 {
+        indices.push(offset);
+        indices.push(offset + 1 + corner);
+        indices.push(offset + 1 + (corner + 1) % 6);
+    }
+}
+
+// Every cell gets a grey hexagon first and a slightly smaller tile on top, so the grey shows between them as grid lines
+
+pub fn draw_hex_level(vertices: &mut Vec<ColorVertex>, indices: &mut Vec<u16>, level: &Level, origin: (f32, f32), radius: f32) {
+    for y in 0..level.height {
+        for x in 0..level.width {
+            draw_color_hexagon(vertices, indices, hex_center(origin, radius, (x, y)), radius, (DARKGREY.0, DARKGREY.1, DARKGREY.2, 255));
+        }
+    }
+
+    for y in 0..level.height {
+        for x in 0..level.width {
+            let color = tile_color(level.get((x, y)));
+
+            draw_color_hexagon(vertices, indices, hex_center(origin, radius, (x, y)), radius - 1.0, (color.0, color.1, color.2, 255));
+        }
+    }
+}
+
+// The runner is drawn around a center in pixels, which may fall between two cells while it moves, the scale squashes it
+
+pub fn draw_runner(vertices: &mut Vec<ColorVertex>, indices: &mut Vec<u16>, (center_x, center_y): (f32, f32), (scale_x, scale_y): (f32, f32), size: f32) {
+    let (width, height) = (size * scale_x, size * scale_y);
+
+    draw_color_quad(vertices, indices, ((center_x - width / 2.0).round() as u32, (center_y - height / 2.0).round() as u32), (width.round() as u32, height.round() as u32), (IVORY.0, IVORY.1, IVORY.2, 255));
 }
\ No newline at end of file
@@ -0,0 +1,18 @@
+use wgpu::{Device, Queue, RenderPass};
+
+use crate::logic::scene::{AsAny, Scene};
+use crate::settings::Settings;
+
+pub trait SceneRenderer: AsAny + 'static {
+    fn update(&mut self, _logic: &dyn Scene, _alpha: f32, _device: &Device, _queue: &Queue) {}
+
+    fn configure(&mut self, _settings: &Settings) {}
+
+    fn process_resize(&mut self, _size: (u32, u32), _scale_factor: f32, _queue: &Queue) {}
+
+    // Lets a renderer place logic elements that depend on measured text, like the menu grid below the title
+
+    fn layout(&self, _logic: &mut dyn Scene) {}
+
+    fn render<'pass>(&'pass self, _render_pass: &mut RenderPass<'pass>) {}
+}
\ No newline at end of file
@@ -0,0 +1,195 @@
+use std::rc::Rc;
+use glam::Mat4;
+use wgpu::{BindGroup, Buffer, Device, Queue, RenderPass, SurfaceConfiguration};
+use wgpu::util::DeviceExt;
+use wgpu_text::glyph_brush::ab_glyph::FontRef;
+use wgpu_text::{BrushBuilder, TextBrush};
+use wgpu_text::glyph_brush::{Section, Text};
+use winit::dpi::PhysicalPosition;
+
+use crate::logic::settings::SettingsLogic;
+use crate::logic::scene::Scene;
+use crate::renderer::ColorVertex;
+use crate::renderer::scene::SceneRenderer;
+use crate::renderer::utils::draw_text_box;
+use crate::renderer::utils::palette::{DARKGREY, IVORY, YELLOW};
+use crate::renderer::utils::pipeline::ColorPipeline;
+
+const ROW_TOP: u32 = 150;
+const ROW_HEIGHT: u32 = 45;
+
+pub struct SettingsRenderer {
+    brush: TextBrush<FontRef<'static>>,
+    projection_view_model_uniform: Buffer,
+    vertex_buffer: Buffer,
+    index_buffer: Buffer,
+    indices_count: usize,
+    bind_group: BindGroup,
+
+    size: (u32, u32),
+    scale_factor: f32,
+    selected: Option<usize>,
+    lines: Vec<String>,
+
+    color: Rc<ColorPipeline>,
+}
+
+impl SettingsRenderer {
+    pub fn new(logic: &SettingsLogic, color: Rc<ColorPipeline>, device: &Device, queue: &Queue, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
+        let font: &[u8] = include_bytes!("fonts/BulletTrace7-rppO.ttf");
+        let brush = BrushBuilder::using_font_bytes(font).unwrap().build(
+            device,
+            config.width,
+            config.height,
+            config.format,
+        );
+
+        let size = ((config.width as f32 / scale_factor) as u32, (config.height as f32 / scale_factor) as u32);
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, size.0 as f32, size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+        let projection_view_model_uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Uniform Buffer"),
+            contents: bytemuck::cast_slice(projection_view_model_ref),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Settings BindGroup"),
+            layout: &color.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: projection_view_model_uniform.as_entire_binding(),
+                },
+            ],
+        });
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build_selection(logic, size, device);
+
+        let mut renderer = Self {
+            brush,
+            projection_view_model_uniform,
+            vertex_buffer,
+            index_buffer,
+            indices_count,
+            bind_group,
+            size,
+            scale_factor,
+            selected: None,
+            lines: Vec::new(),
+            color,
+        };
+
+        renderer.update(logic, 0.0, device, queue);
+
+        return renderer;
+    }
+
+    fn build_selection(logic: &SettingsLogic, (width, _height): (u32, u32), device: &Device) -> (Buffer, Buffer, usize) {
+        let mut vertex_data: Vec<ColorVertex> = Vec::new();
+        let mut index_data: Vec<u16> = Vec::new();
+
+        let up = ROW_TOP + logic.selected as u32 * ROW_HEIGHT;
+
+        draw_text_box(&mut vertex_data, &mut index_data, (150, up), (width.saturating_sub(300).max(1), ROW_HEIGHT - 10), 0, 3);
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Settings VertexBuffer"),
+            contents: bytemuck::cast_slice(&vertex_data),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Settings IndexBuffer"),
+            contents: bytemuck::cast_slice(&index_data),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        return (vertex_buffer, index_buffer, index_data.len());
+    }
+}
+
+impl SceneRenderer for SettingsRenderer {
+    fn update(&mut self, logic: &dyn Scene, _alpha: f32, device: &Device, queue: &Queue) {
+        let logic = logic.as_any().downcast_ref::<SettingsLogic>().expect("SettingsRenderer expects a SettingsLogic");
+
+        let mut lines = Vec::new();
+
+        for (label, value) in logic.rows() {
+            lines.push(label.to_string());
+            lines.push(value);
+        }
+
+        lines.push("Left/Right change   Escape save".to_string());
+
+        if self.selected == Some(logic.selected) && lines == self.lines {
+            return;
+        }
+
+        let (vertex_buffer, index_buffer, indices_count) = Self::build_selection(logic, self.size, device);
+
+        self.vertex_buffer = vertex_buffer;
+        self.index_buffer = index_buffer;
+        self.indices_count = indices_count;
+
+        let scale_factor = self.scale_factor;
+        let ivory = [IVORY.0 as f32 / 255.0, IVORY.1 as f32 / 255.0, IVORY.2 as f32 / 255.0, 1.0];
+        let yellow = [YELLOW.0 as f32 / 255.0, YELLOW.1 as f32 / 255.0, YELLOW.2 as f32 / 255.0, 1.0];
+        let grey = [DARKGREY.0 as f32 / 255.0, DARKGREY.1 as f32 / 255.0, DARKGREY.2 as f32 / 255.0, 1.0];
+
+        let mut texts = Vec::<Section>::new();
+
+        texts.push(Section::default()
+            .add_text(Text::new("SETTINGS").with_scale(60.0 * scale_factor).with_color(ivory))
+            .with_screen_position(PhysicalPosition::new(150.0 * scale_factor, 50.0 * scale_factor)));
+
+        let rows = lines[..lines.len() - 1].chunks(2);
+
+        for (i, row) in rows.enumerate() {
+            let up = (ROW_TOP + i as u32 * ROW_HEIGHT) as f32;
+            let color = if i == logic.selected { yellow } else { ivory };
+
+            texts.push(Section::default()
+                .add_text(Text::new(&row[0]).with_scale(32.0 * scale_factor).with_color(color))
+                .with_screen_position(PhysicalPosition::new(170.0 * scale_factor, up * scale_factor)));
+
+            texts.push(Section::default()
+                .add_text(Text::new(&row[1]).with_scale(32.0 * scale_factor).with_color(color))
+                .with_screen_position(PhysicalPosition::new(600.0 * scale_factor, up * scale_factor)));
+        }
+
+        texts.push(Section::default()
+            .add_text(Text::new(lines.last().unwrap()).with_scale(32.0 * scale_factor).with_color(grey))
+            .with_screen_position(PhysicalPosition::new(150.0 * scale_factor, (self.size.1 as f32 - 70.0) * scale_factor)));
+
+        self.brush.queue(device, queue, texts).expect("Failed to draw settings text");
+
+        self.selected = Some(logic.selected);
+        self.lines = lines;
+    }
+
+    fn process_resize(&mut self, (width, height): (u32, u32), scale_factor: f32, queue: &Queue) {
+        self.brush.resize_view(width as f32, height as f32, queue);
+
+        self.size = ((width as f32 / scale_factor) as u32, (height as f32 / scale_factor) as u32);
+        self.scale_factor = scale_factor;
+        self.selected = None;
+
+        let projection_view_model_data = Mat4::orthographic_rh(0f32, self.size.0 as f32, self.size.1 as f32, 0f32, -1f32, 1f32);
+        let projection_view_model_ref: &[f32; 16] = projection_view_model_data.as_ref();
+
+        queue.write_buffer(&self.projection_view_model_uniform, 0, bytemuck::cast_slice(projection_view_model_ref));
+    }
+
+    fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
+        render_pass.set_pipeline(&self.color.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.draw_indexed(0..self.indices_count as u32, 0, 0..1);
+
+        self.brush.draw(render_pass);
+    }
+}
@@ -0,0 +1,82 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) texture_coordinate: vec2<f32>,
+};
+
+struct Transition {
+    progress: f32,
+    style: u32,
+    aspect: f32,
+    padding: f32,
+};
+
+@group(0)
+@binding(0)
+var<uniform> transition: Transition;
+
+@group(0)
+@binding(1)
+var from_image: texture_2d<f32>;
+
+@group(0)
+@binding(2)
+var to_image: texture_2d<f32>;
+
+@group(0)
+@binding(3)
+var texture_sampler: sampler;
+
+// One triangle covering the whole screen, no vertex buffer needed
+
+@vertex
+fn vs_main(
+
+    @builtin(vertex_index) index: u32,
+
+) -> VertexOutput {
+    var result: VertexOutput;
+
+    let texture_coordinate = vec2<f32> (f32((index << 1u) & 2u), f32(index & 2u));
+
+    result.position = vec4<f32> (texture_coordinate.x * 2.0 - 1.0, 1.0 - texture_coordinate.y * 2.0, 0.0, 1.0);
+    result.texture_coordinate = texture_coordinate;
+
+    return result;
+}
+
+@fragment
+fn fs_main(
+
+    vertex: VertexOutput
+
+    ) -> @location(0) vec4<f32> {
+    let progress = transition.progress;
+    let coordinate = vertex.texture_coordinate;
+
+    // Sample both images up front, sampling must stay in uniform control flow
+
+    let slide = coordinate.x < 1.0 - progress;
+    let from_coordinate = select(coordinate, coordinate + vec2<f32> (progress, 0.0), transition.style == 2u);
+    let to_coordinate = select(coordinate, coordinate - vec2<f32> (1.0 - progress, 0.0), transition.style == 2u);
+
+    let from_color = textureSample(from_image, texture_sampler, from_coordinate);
+    let to_color = textureSample(to_image, texture_sampler, to_coordinate);
+
+    switch transition.style {
+        case 1u: {
+            return mix(from_color, to_color, progress);
+        }
+        case 2u: {
+            return select(to_color, from_color, slide);
+        }
+        case 3u: {
+            let offset = (coordinate - vec2<f32> (0.5, 0.5)) * vec2<f32> (transition.aspect, 1.0);
+            let radius = progress * length(vec2<f32> (transition.aspect, 1.0)) * 0.5;
+
+            return select(from_color, to_color, length(offset) <= radius);
+        }
+        default: {
+            return to_color;
+        }
+    }
+}
\ No newline at end of file
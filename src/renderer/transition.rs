@@ -0,0 +1,207 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::{BindGroup, Buffer, Device, Queue, RenderPass, SurfaceConfiguration, TextureFormat, TextureView};
+use wgpu::util::DeviceExt;
+
+use crate::renderer::utils::pipeline::TransitionPipeline;
+use crate::settings::TransitionStyle;
+use crate::sprint_the_game::State;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct TransitionUniform {
+    progress: f32,
+    style: u32,
+    aspect: f32,
+    padding: f32,
+}
+
+// The view keeps its texture alive, so only the view is stored
+
+pub struct RenderTarget {
+    pub view: TextureView,
+}
+
+impl RenderTarget {
+    fn new(device: &Device, format: TextureFormat, (width, height): (u32, u32)) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            label: Some("Transition RenderTarget"),
+            view_formats: &[],
+        });
+
+        return Self {
+            view: texture.create_view(&wgpu::TextureViewDescriptor::default()),
+        };
+    }
+}
+
+pub struct TransitionRenderer {
+    pipeline: TransitionPipeline,
+    uniform: Buffer,
+    bind_group: BindGroup,
+
+    pub from: RenderTarget,
+    pub to: RenderTarget,
+
+    format: TextureFormat,
+    size: (u32, u32),
+
+    style: TransitionStyle,
+    duration: f32,
+    elapsed: f32,
+    outgoing: Vec<State>,
+    shown: Vec<State>,
+    snapshot: bool,
+}
+
+impl TransitionRenderer {
+    pub fn new(device: &Device, config: &SurfaceConfiguration) -> Self {
+        let pipeline = TransitionPipeline::new(device, config);
+        let size = (config.width, config.height);
+
+        let uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Transition Uniform Buffer"),
+            contents: bytemuck::bytes_of(&TransitionUniform {
+                progress: 0.0,
+                style: 0,
+                aspect: 1.0,
+                padding: 0.0,
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let from = RenderTarget::new(device, config.format, size);
+        let to = RenderTarget::new(device, config.format, size);
+        let bind_group = Self::build_bind_group(&pipeline, &uniform, &from, &to, device);
+
+        return Self {
+            pipeline,
+            uniform,
+            bind_group,
+            from,
+            to,
+            format: config.format,
+            size,
+            style: TransitionStyle::Fade,
+            duration: 0.4,
+            elapsed: 0.0,
+            outgoing: Vec::new(),
+            shown: Vec::new(),
+            snapshot: false,
+        };
+    }
+
+    fn build_bind_group(pipeline: &TransitionPipeline, uniform: &Buffer, from: &RenderTarget, to: &RenderTarget, device: &Device) -> BindGroup {
+        return device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Transition BindGroup"),
+            layout: &pipeline.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&from.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&to.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Sampler(&pipeline.sampler),
+                },
+            ],
+        });
+    }
+
+    pub fn configure(&mut self, style: TransitionStyle, duration: f32) {
+        self.style = style;
+        self.duration = duration;
+    }
+
+    pub fn is_active(&self) -> bool {
+        return !self.outgoing.is_empty();
+    }
+
+    pub fn outgoing(&self) -> &[State] {
+        return &self.outgoing;
+    }
+
+    // The outgoing states are drawn once when the transition starts, later frames reuse that picture
+
+    pub fn take_snapshot(&mut self) -> bool {
+        return std::mem::take(&mut self.snapshot);
+    }
+
+    // Starts a transition whenever the scene at the bottom of the visible states changes, the first states shown
+    // cut in directly and overlays like pause or victory open and close over the scene below without one
+
+    pub fn tick(&mut self, states: &[State], delta_time: f32) {
+        if self.shown != states {
+            if !self.shown.is_empty() && self.shown.first() != states.first() && self.style != TransitionStyle::Cut && self.duration > 0.0 {
+                self.outgoing = self.shown.clone();
+                self.elapsed = 0.0;
+                self.snapshot = true;
+            }
+
+            self.shown = states.to_vec();
+
+            return;
+        }
+
+        if self.is_active() {
+            self.elapsed += delta_time;
+
+            if self.elapsed >= self.duration {
+                self.outgoing.clear();
+            }
+        }
+    }
+
+    pub fn update(&mut self, queue: &Queue) {
+        if !self.is_active() {
+            return;
+        }
+
+        let style = match self.style {
+            TransitionStyle::Cut => 0,
+            TransitionStyle::Fade => 1,
+            TransitionStyle::Slide => 2,
+            TransitionStyle::Iris => 3,
+        };
+
+        let uniform = TransitionUniform {
+            progress: (self.elapsed / self.duration).clamp(0.0, 1.0),
+            style,
+            aspect: self.size.0 as f32 / self.size.1.max(1) as f32,
+            padding: 0.0,
+        };
+
+        queue.write_buffer(&self.uniform, 0, bytemuck::bytes_of(&uniform));
+    }
+
+    pub fn process_resize(&mut self, (width, height): (u32, u32), device: &Device) {
+        self.size = (width, height);
+        self.from = RenderTarget::new(device, self.format, self.size);
+        self.to = RenderTarget::new(device, self.format, self.size);
+        self.bind_group = Self::build_bind_group(&self.pipeline, &self.uniform, &self.from, &self.to, device);
+        self.snapshot = self.is_active();
+    }
+
+    pub fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
+        render_pass.set_pipeline(&self.pipeline.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
\ No newline at end of file
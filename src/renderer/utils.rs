@@ -1,5 +1,5 @@
 use crate::renderer::ColorVertex;
-use crate::renderer::utils::palette::{BLACK, DARKBLUE, INCREASED_DARKBLUE, IVORY};
+use crate::renderer::utils::palette::{BLACK, DARKBLUE, IVORY};
 use crate::renderer::utils::quad::draw_color_quad;
 
 pub mod palette;
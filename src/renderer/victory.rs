@@ -1,22 +1,49 @@
 use std::rc::Rc;
-use wgpu::{Queue, RenderPass};
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+
 use crate::logic::victory::VictoryLogic;
-use crate::renderer::utils::pipeline::{ColorPipeline, TexturePipeline};
+use crate::logic::scene::Scene;
+use crate::renderer::panel::PanelRenderer;
+use crate::renderer::scene::SceneRenderer;
+use crate::renderer::utils::pipeline::ColorPipeline;
 
 pub struct VictoryRenderer {
-    color: Rc<ColorPipeline>,
-    texture: Rc<TexturePipeline>,
+    panel: PanelRenderer,
 }
 
 impl VictoryRenderer {
-    pub fn new(logic: &VictoryLogic, color: Rc<ColorPipeline>, texture: Rc<TexturePipeline>) -> Self {
-        return Self {
-            color,
-            texture
+    pub fn new(logic: &VictoryLogic, color: Rc<ColorPipeline>, device: &Device, queue: &Queue, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
+        let mut renderer = Self {
+            panel: PanelRenderer::new(color, device, config, scale_factor),
         };
+
+        renderer.update(logic, 0.0, device, queue);
+
+        return renderer;
     }
+}
+
+impl SceneRenderer for VictoryRenderer {
+    fn update(&mut self, logic: &dyn Scene, _alpha: f32, device: &Device, queue: &Queue) {
+        let logic = logic.as_any().downcast_ref::<VictoryLogic>().expect("VictoryRenderer expects a VictoryLogic");
+
+        let mut lines = vec!["LEVEL COMPLETE".to_string()];
+
+        if let Some(result) = &logic.result {
+            lines.push(result.name.clone());
+            lines.push(format!("Moves: {}", result.moves));
+            lines.push(format!("Time: {:.2} s", result.time));
+            lines.push(format!("Stars: {}/3", result.stars));
+        }
 
-    pub fn update(&mut self, logic: &VictoryLogic) {}
-    pub fn process_resize(&mut self, (width, height): (u32, u32), queue: &Queue) {}
-    pub fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {}
-}
\ No newline at end of file
+        self.panel.update(&logic.panel, lines, device, queue);
+    }
+
+    fn process_resize(&mut self, size: (u32, u32), scale_factor: f32, queue: &Queue) {
+        self.panel.process_resize(size, scale_factor, queue);
+    }
+
+    fn render<'pass>(&'pass self, render_pass: &mut RenderPass<'pass>) {
+        self.panel.render(render_pass);
+    }
+}
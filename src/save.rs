@@ -0,0 +1,166 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{data_dir, write_atomic};
+
+pub const SAVE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LevelRecord {
+    pub id: u32,
+    pub completed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_moves: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_time: Option<f32>,
+    #[serde(default)]
+    pub stars: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SaveGame {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub levels: Vec<LevelRecord>,
+
+    // Set for saves from a newer version, which are shown but never written over
+    #[serde(skip)]
+    pub read_only: bool,
+}
+
+#[derive(Deserialize)]
+struct SaveHeader {
+    #[serde(default)]
+    version: u32,
+}
+
+impl SaveGame {
+    pub fn new() -> Self {
+        return Self {
+            version: SAVE_VERSION,
+            levels: Vec::new(),
+            read_only: false,
+        };
+    }
+
+    pub fn path() -> PathBuf {
+        return data_dir().join("save.toml");
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+        let header: SaveHeader = toml::from_str(&content).map_err(|error| format!("Failed to parse {}: {}", path.display(), error))?;
+
+        // A newer version may have changed the format, keep whatever still parses
+
+        if header.version > SAVE_VERSION {
+            let mut save = toml::from_str::<Self>(&content).unwrap_or_default();
+
+            save.version = header.version;
+            save.read_only = true;
+
+            return Ok(save);
+        }
+
+        let mut save: Self = toml::from_str(&content).map_err(|error| format!("Failed to parse {}: {}", path.display(), error))?;
+
+        save.migrate();
+
+        return Ok(save);
+    }
+
+    pub fn load_or_default(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::new();
+        }
+
+        let save = Self::load(path).unwrap_or_else(|error| {
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+            let backup = path.with_extension(format!("toml.corrupt-{}", timestamp));
+
+            match fs::rename(path, &backup) {
+                Ok(()) => eprintln!("{}, moved it to {} and starting without progress", error, backup.display()),
+                Err(rename_error) => eprintln!("{}, failed to back it up: {}", error, rename_error),
+            }
+
+            Self::new()
+        });
+
+        if save.read_only {
+            eprintln!("{} was written by a newer version ({} > {}), progress made now will not be saved", path.display(), save.version, SAVE_VERSION);
+        }
+
+        return save;
+    }
+
+    // Each step upgrades by one version, so an old file passes through every step after its own
+
+    fn migrate(&mut self) {
+        if self.version < 1 {
+            // Version 0 had no writer that merged records, so a level could be listed more than once
+
+            let mut merged: Vec<LevelRecord> = Vec::new();
+
+            for record in self.levels.drain(..) {
+                match merged.iter_mut().find(|existing| existing.id == record.id) {
+                    Some(existing) => {
+                        existing.completed |= record.completed;
+                        existing.stars = existing.stars.max(record.stars);
+                    }
+                    None => merged.push(record),
+                }
+            }
+
+            merged.sort_by_key(|record| record.id);
+
+            self.levels = merged;
+        }
+
+        self.version = SAVE_VERSION;
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if self.read_only {
+            return Err(format!("Not saving progress, {} was written by a newer version", path.display()));
+        }
+
+        let content = toml::to_string(self).map_err(|error| format!("Failed to serialize save: {}", error))?;
+
+        return write_atomic(path, &content);
+    }
+
+    pub fn record(&self, id: u32) -> Option<&LevelRecord> {
+        return self.levels.iter().find(|record| record.id == id);
+    }
+
+    pub fn record_completion(&mut self, id: u32, moves: u32, time: f32, stars: u32) {
+        let record = match self.levels.iter().position(|record| record.id == id) {
+            Some(i) => &mut self.levels[i],
+            None => {
+                self.levels.push(LevelRecord {
+                    id,
+                    ..Default::default()
+                });
+
+                self.levels.last_mut().unwrap()
+            }
+        };
+
+        record.completed = true;
+        record.best_moves = Some(record.best_moves.map_or(moves, |best| best.min(moves)));
+        record.best_time = Some(record.best_time.map_or(time, |best| best.min(time)));
+        record.stars = record.stars.max(stars);
+    }
+
+    pub fn is_completed(&self, id: u32) -> bool {
+        return self.record(id).is_some_and(|record| record.completed);
+    }
+
+    pub fn total_stars(&self) -> u32 {
+        return self.levels.iter().map(|record| record.stars).sum();
+    }
+}
@@ -0,0 +1,119 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{config_dir, write_atomic};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowMode {
+    Windowed,
+    Fullscreen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    Classic,
+    HighContrast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransitionStyle {
+    Cut,
+    Fade,
+    Slide,
+    Iris,
+}
+
+impl TransitionStyle {
+    pub fn cycle(&self, step: i32) -> Self {
+        let styles = [TransitionStyle::Cut, TransitionStyle::Fade, TransitionStyle::Slide, TransitionStyle::Iris];
+        let i = styles.iter().position(|style| style == self).unwrap() as i32;
+
+        return styles[(i + step).rem_euclid(styles.len() as i32) as usize];
+    }
+}
+
+// How the runner moves between the cells of a slide, bounce springs back off whatever stopped it
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SlideEasing {
+    Accelerate,
+    Constant,
+    Bounce,
+}
+
+impl SlideEasing {
+    pub fn cycle(&self, step: i32) -> Self {
+        let easings = [SlideEasing::Accelerate, SlideEasing::Constant, SlideEasing::Bounce];
+        let i = easings.iter().position(|easing| easing == self).unwrap() as i32;
+
+        return easings[(i + step).rem_euclid(easings.len() as i32) as usize];
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub window_mode: WindowMode,
+    pub vsync: bool,
+    pub animations: bool,
+    pub animation_speed: f32,
+    pub slide_easing: SlideEasing,
+    pub volume: f32,
+    pub theme: Theme,
+    pub show_fps: bool,
+    pub transition: TransitionStyle,
+    pub transition_duration: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        return Self {
+            window_mode: WindowMode::Windowed,
+            vsync: true,
+            animations: true,
+            animation_speed: 1.0,
+            slide_easing: SlideEasing::Accelerate,
+            volume: 0.8,
+            theme: Theme::Classic,
+            show_fps: true,
+            transition: TransitionStyle::Fade,
+            transition_duration: 0.4,
+        };
+    }
+
+    pub fn path() -> PathBuf {
+        return config_dir().join("settings.toml");
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+
+        return toml::from_str(&content).map_err(|error| format!("Failed to parse {}: {}", path.display(), error));
+    }
+
+    pub fn load_or_default(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::new();
+        }
+
+        return Self::load(path).unwrap_or_else(|error| {
+            eprintln!("{}, using default settings", error);
+
+            Self::new()
+        });
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = toml::to_string(self).map_err(|error| format!("Failed to serialize settings: {}", error))?;
+
+        return write_atomic(path, &content);
+    }
+}
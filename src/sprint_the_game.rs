@@ -1,78 +1,307 @@
 use std::cmp::PartialEq;
-use wgpu::{Device, Surface, Queue, SurfaceConfiguration, Adapter};
-use winit::event::{ElementState, KeyEvent, MouseButton};
+use std::path::PathBuf;
+#[cfg(feature = "render")]
+use wgpu::{Device, Surface, Queue, SurfaceConfiguration};
+#[cfg(feature = "render")]
+use winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta};
 
+use crate::logic::play::level::Level;
+use crate::logic::victory::LevelResult;
+#[cfg(feature = "render")]
+use crate::cli::{LevelArgument, Options};
+#[cfg(feature = "render")]
+use crate::settings::Settings;
+#[cfg(feature = "render")]
 use crate::{
-    logic::Logic,
+    bindings::Bindings,
+    logic::{bindings::BindingsLogic, event::GameEvent, input::KeyCapture, menu::MenuLogic, settings::SettingsLogic, Logic},
     renderer::Renderer,
 };
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum State {
     Menu,
     Play,
+    Pause,
     Victory,
     Edit,
+    Bindings,
+    Settings,
 }
 
-pub struct StateTransition {
-    next_state: State,
+impl State {
+    pub fn is_overlay(&self) -> bool {
+        return matches!(self, State::Pause | State::Victory);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Payload {
+    Level(u32),
+    NextLevel,
+    Retry,
+    Path(PathBuf),
+    Result(LevelResult),
+    Playtest(Level),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transition {
+    Push(State, Option<Payload>),
+    Replace(State, Option<Payload>),
+    Pop,
+    Quit,
 }
 
-impl StateTransition {
-    pub fn new() -> Self {
+pub struct StateStack {
+    states: Vec<State>,
+    pending: Vec<Transition>,
+    quit: bool,
+}
+
+impl StateStack {
+    pub fn new(root: State) -> Self {
         return Self {
-            next_state: State::Menu
+            states: vec![root],
+            pending: Vec::new(),
+            quit: false,
         };
     }
-    pub fn set_next_state(&mut self, state: State) {
-        self.next_state = state;
+
+    pub fn top(&self) -> State {
+        return *self.states.last().unwrap();
+    }
+
+    pub fn states(&self) -> &[State] {
+        return &self.states;
+    }
+
+    pub fn visible(&self) -> &[State] {
+        let base = self.states.iter().rposition(|state| !state.is_overlay()).unwrap_or(0);
+
+        return &self.states[base..];
+    }
+
+    pub fn push(&mut self, state: State) {
+        self.pending.push(Transition::Push(state, None));
+    }
+
+    pub fn push_with(&mut self, state: State, payload: Payload) {
+        self.pending.push(Transition::Push(state, Some(payload)));
+    }
+
+    pub fn replace(&mut self, state: State) {
+        self.pending.push(Transition::Replace(state, None));
+    }
+
+    pub fn replace_with(&mut self, state: State, payload: Payload) {
+        self.pending.push(Transition::Replace(state, Some(payload)));
+    }
+
+    pub fn pop(&mut self) {
+        self.pending.push(Transition::Pop);
+    }
+
+    pub fn quit(&mut self) {
+        self.pending.push(Transition::Quit);
+    }
+
+    pub fn should_quit(&self) -> bool {
+        return self.quit;
+    }
+
+    pub fn apply(&mut self) -> Vec<(State, Option<Payload>)> {
+        let mut entered = Vec::new();
+
+        for transition in self.pending.drain(..) {
+            match transition {
+                Transition::Push(state, payload) => {
+                    self.states.push(state);
+                    entered.push((state, payload));
+                }
+                Transition::Replace(state, payload) => {
+                    self.states.pop();
+                    self.states.push(state);
+                    entered.push((state, payload));
+                }
+                Transition::Pop => {
+                    self.states.pop();
+
+                    if self.states.is_empty() {
+                        self.quit = true;
+                    }
+                }
+                Transition::Quit => self.quit = true,
+            }
+        }
+
+        if self.states.is_empty() {
+            self.states.push(State::Menu);
+        }
+
+        return entered;
     }
 }
 
+#[cfg(feature = "render")]
 pub struct Application {
-    state: State,
-    transition: StateTransition,
+    stack: StateStack,
 
+    bindings: Bindings,
     logic: Logic,
     renderer: Renderer,
+
+    settings_change: Option<Settings>,
 }
 
+#[cfg(feature = "render")]
 impl Application {
-    pub fn new(device: &Device, surface: &Surface, adapter: &Adapter, queue: &Queue, config: &SurfaceConfiguration) -> Self {
-        let state = State::Menu;
-        let transition = StateTransition::new();
-        let logic = Logic::new();
-        let renderer = Renderer::new(&logic.menu, &logic.play, &logic.victory, &logic.edit, device, surface, adapter, queue, config);
+    pub fn new(device: &Device, queue: &Queue, config: &SurfaceConfiguration, scale_factor: f32) -> Self {
+        let stack = StateStack::new(State::Menu);
+        let mut logic = Logic::new();
+        let key_bindings = logic.scene::<BindingsLogic>(&State::Bindings).map(|bindings| bindings.key_bindings.clone()).unwrap_or_default();
+        let bindings = Bindings::new(&key_bindings);
+        let mut renderer = Renderer::new(&logic, device, queue, config, scale_factor);
+
+        let settings = logic.scene::<SettingsLogic>(&State::Settings).map(SettingsLogic::effective).unwrap_or_default();
+        renderer.configure(&settings);
+
+        logic.layout(((config.width as f32 / scale_factor) as u32, (config.height as f32 / scale_factor) as u32));
+        renderer.layout(&mut logic);
 
         return Self {
-            state,
-            transition,
+            stack,
+            bindings,
             logic,
             renderer,
+            settings_change: None,
         };
     }
 
+    pub fn apply_options(&mut self, options: &Options) -> Result<(), String> {
+        if let Some(settings) = self.logic.scene_mut::<SettingsLogic>(&State::Settings) {
+            settings.window_override = options.window_mode;
+        }
+
+        if let Some(replay) = &options.replay {
+            return Err(format!("cannot play {}: replays are not supported yet", replay.display()));
+        }
+
+        if let Some(LevelArgument::File(level)) = &options.level {
+            self.stack.push_with(State::Play, Payload::Playtest(level.clone()));
+        }
+
+        if let Some(LevelArgument::Id(id)) = options.level {
+            let locked = self.logic.scene::<MenuLogic>(&State::Menu)
+                .is_some_and(|menu| menu.level_buttons.iter().any(|level_button| level_button.id == id && level_button.locked));
+
+            if locked {
+                return Err(format!("level {} is locked, complete level {} first", id, id.saturating_sub(1)));
+            }
+
+            self.stack.replace_with(State::Play, Payload::Level(id));
+        }
+
+        if let Some(path) = &options.edit {
+            self.stack.replace_with(State::Edit, Payload::Path(path.clone()));
+        }
+
+        self.apply_transitions();
+
+        return Ok(());
+    }
+
     pub fn process_keyboard(&mut self, key_event: KeyEvent) {
-        self.logic.process_keyboard(&self.state, key_event);
+        let top = self.stack.top();
+
+        match self.logic.capture(&top) {
+            KeyCapture::Actions => {
+                if let Some(action) = self.bindings.map_keyboard(&key_event) {
+                    self.logic.process_action(&top, action);
+                }
+            }
+            KeyCapture::Key => {
+                if let Some(key) = Bindings::key_name(&key_event) {
+                    self.logic.process_key(&top, key);
+                }
+            }
+            KeyCapture::Text => {
+                if let Some(input) = Bindings::text_input(&key_event) {
+                    self.logic.process_text(&top, input);
+                }
+            }
+        }
     }
 
     pub fn process_mouse(&mut self, element_state: ElementState, mouse_button: MouseButton) {
-        self.logic.process_mouse(&self.state, element_state, mouse_button);
+        if let Some(action) = self.bindings.map_mouse(element_state, mouse_button) {
+            self.logic.process_action(&self.stack.top(), action);
+        }
+    }
+
+    pub fn process_wheel(&mut self, delta: MouseScrollDelta) {
+        if let Some(action) = self.bindings.map_wheel(delta) {
+            self.logic.process_action(&self.stack.top(), action);
+        }
+    }
+
+    pub fn process_cursor(&mut self, position: (f32, f32)) {
+        self.logic.process_cursor(&self.stack.top(), position);
+    }
+
+    pub fn process_cursor_left(&mut self) {
+        self.logic.process_cursor_left(&self.stack.top());
     }
 
     pub fn update(&mut self, delta_time: f32) {
-        self.logic.update(&self.state, &mut self.transition, delta_time);
-        self.renderer.update(&self.state, &self.logic.menu, &self.logic.play, &self.logic.victory, &self.logic.edit);
+        self.logic.update(&self.stack.top(), &mut self.stack, delta_time);
+
+        self.apply_transitions();
+
+        self.renderer.tick(self.stack.visible(), delta_time);
+
+        for event in self.logic.dispatch_events() {
+            match event {
+                GameEvent::BindingsChanged(key_bindings) => self.bindings = Bindings::new(&key_bindings),
+                GameEvent::SettingsChanged(settings) => {
+                    self.renderer.configure(&settings);
+                    self.settings_change = Some(settings);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn apply_transitions(&mut self) {
+        for (state, payload) in self.stack.apply() {
+            self.logic.enter(&state, payload);
+        }
+    }
+
+    pub fn interpolate(&mut self, alpha: f32, device: &Device, queue: &Queue) {
+        self.renderer.update(self.stack.visible(), &self.logic, alpha, device, queue);
+    }
+
+    pub fn settings(&self) -> Settings {
+        return self.logic.scene::<SettingsLogic>(&State::Settings).map(SettingsLogic::effective).unwrap_or_default();
+    }
+
+    pub fn take_settings_change(&mut self) -> Option<Settings> {
+        return self.settings_change.take();
+    }
 
-        self.state = self.transition.next_state;
+    pub fn should_quit(&self) -> bool {
+        return self.stack.should_quit();
     }
 
-    pub fn process_resize(&mut self, (width, height): (u32, u32), queue: &Queue) {
-        self.renderer.process_resize((width, height), queue);
+    pub fn process_resize(&mut self, (width, height): (u32, u32), scale_factor: f32, device: &Device, queue: &Queue) {
+        self.renderer.process_resize((width, height), scale_factor, device, queue);
+
+        self.logic.layout(((width as f32 / scale_factor) as u32, (height as f32 / scale_factor) as u32));
+        self.renderer.layout(&mut self.logic);
     }
 
-    pub fn render(&self, device: &Device, surface: &Surface, queue: &Queue) {
-        self.renderer.render(&self.state, device, surface, queue);
+    pub fn render(&mut self, device: &Device, surface: &Surface, queue: &Queue) {
+        self.renderer.render(self.stack.visible(), device, surface, queue);
     }
 }
\ No newline at end of file
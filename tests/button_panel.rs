@@ -0,0 +1,57 @@
+use sprint_the_game::logic::input::InputAction;
+use sprint_the_game::logic::panel::{Arrangement, ButtonPanel};
+
+fn panel(arrangement: Arrangement) -> ButtonPanel<u32> {
+    let mut panel = ButtonPanel::new(arrangement, (300, 400), (100, 50), 20);
+
+    panel.set_buttons(&[(0, "First"), (1, "Second")]);
+    panel.layout((1000, 800));
+
+    return panel;
+}
+
+#[test]
+fn rows_are_centered_along_the_bottom_of_the_panel() {
+    let panel = panel(Arrangement::Row);
+
+    assert_eq!(panel.position, (350, 200));
+    assert_eq!(panel.buttons[0].position, (390, 530));
+    assert_eq!(panel.buttons[1].position, (510, 530));
+}
+
+#[test]
+fn columns_end_one_gap_above_the_bottom_of_the_panel() {
+    let panel = panel(Arrangement::Column);
+
+    assert_eq!(panel.buttons[0].position, (450, 460));
+    assert_eq!(panel.buttons[1].position, (450, 530));
+}
+
+#[test]
+fn focus_follows_the_arrangement_and_stops_at_the_ends() {
+    let mut panel = panel(Arrangement::Column);
+
+    assert_eq!(panel.process_action(InputAction::MoveRight), None);
+    assert_eq!(panel.focused, 0);
+
+    panel.process_action(InputAction::MoveDown);
+    panel.process_action(InputAction::MoveDown);
+
+    assert_eq!(panel.focused, 1);
+    assert_eq!(panel.process_action(InputAction::Confirm), Some(1));
+}
+
+#[test]
+fn clicks_choose_the_hovered_button() {
+    let mut panel = panel(Arrangement::Row);
+
+    panel.process_cursor((395.0, 535.0));
+
+    assert_eq!(panel.hovered, Some(0));
+    assert_eq!(panel.process_action(InputAction::PrimaryClick), Some(0));
+
+    panel.process_cursor((10.0, 10.0));
+
+    assert_eq!(panel.hovered, None);
+    assert_eq!(panel.process_action(InputAction::PrimaryClick), None);
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::PathBuf;
+
+use sprint_the_game::cli::{LevelArgument, Options};
+use sprint_the_game::logic::play::level::{Level, Tile};
+use sprint_the_game::settings::WindowMode;
+
+mod common;
+
+use common::scratch_directory;
+
+fn parse(args: &[&str]) -> Result<Options, String> {
+    return Options::parse(args.iter().map(|arg| arg.to_string()));
+}
+
+#[test]
+fn no_arguments_give_the_defaults() {
+    assert_eq!(parse(&[]).unwrap(), Options::new());
+}
+
+#[test]
+fn every_option_is_parsed() {
+    let options = parse(&["--level", "2", "--fullscreen", "--size", "800x600", "--seed", "42"]).unwrap();
+
+    assert_eq!(options.level, Some(LevelArgument::Id(2)));
+    assert_eq!(options.window_mode, Some(WindowMode::Fullscreen));
+    assert_eq!(options.size, Some((800, 600)));
+    assert_eq!(options.seed, Some(42));
+    assert!(!options.help);
+
+    let options = parse(&["--edit", "custom.toml", "--replay", "run.replay", "--windowed", "-h"]).unwrap();
+
+    assert_eq!(options.edit, Some(PathBuf::from("custom.toml")));
+    assert_eq!(options.replay, Some(PathBuf::from("run.replay")));
+    assert_eq!(options.window_mode, Some(WindowMode::Windowed));
+    assert!(options.help);
+}
+
+#[test]
+fn level_id_must_be_a_shipped_level() {
+    let last = (Level::builtin_count() - 1).to_string();
+    let past_the_end = Level::builtin_count().to_string();
+
+    assert_eq!(parse(&["--level", &last]).unwrap().level, Some(LevelArgument::Id(Level::builtin_count() - 1)));
+    assert!(parse(&["--level", &past_the_end]).is_err());
+    assert!(parse(&["--level"]).is_err());
+}
+
+#[test]
+fn level_path_is_loaded_as_a_level_file() {
+    let directory = scratch_directory("cli_level_path");
+    let path = directory.join("custom.toml");
+    let no_start = directory.join("no_start.toml");
+    let mut level = Level::new(4, 3);
+
+    level.set((0, 0), Tile::Start);
+    level.save(&path).unwrap();
+    Level::new(4, 3).save(&no_start).unwrap();
+
+    assert_eq!(parse(&["--level", path.to_str().unwrap()]).unwrap().level, Some(LevelArgument::File(level)));
+    assert!(parse(&["--level", no_start.to_str().unwrap()]).is_err());
+    assert!(parse(&["--level", "Cargo.toml"]).is_err());
+    assert!(parse(&["--level", "missing.toml"]).is_err());
+
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn conflicting_and_malformed_arguments_are_rejected() {
+    assert!(parse(&["--windowed", "--fullscreen"]).is_err());
+    assert!(parse(&["--level", "0", "--edit", "custom.toml"]).is_err());
+    assert!(parse(&["--size", "800"]).is_err());
+    assert!(parse(&["--size", "0x600"]).is_err());
+    assert!(parse(&["--seed", "-4"]).is_err());
+}
+
+#[test]
+fn repeating_the_same_window_mode_is_allowed() {
+    assert_eq!(parse(&["--windowed", "--windowed"]).unwrap().window_mode, Some(WindowMode::Windowed));
+}
\ No newline at end of file
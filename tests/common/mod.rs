@@ -0,0 +1,30 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use sprint_the_game::logic::event::EventBus;
+use sprint_the_game::logic::scene::Context;
+use sprint_the_game::save::SaveGame;
+
+// An empty directory per name and test process, so tests running side by side never share files
+
+pub fn scratch_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("sprint_the_game_{}_{}", name, process::id()));
+
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+
+    return directory;
+}
+
+// A fresh save that writes into its own scratch directory
+
+pub fn scratch_context(name: &str) -> Context {
+    return Context {
+        save: SaveGame::new(),
+        save_path: scratch_directory(name).join("save.toml"),
+        events: EventBus::new(),
+    };
+}
\ No newline at end of file
@@ -0,0 +1,198 @@
+use std::fs;
+
+use sprint_the_game::logic::edit::{Dialog, EditLogic};
+use sprint_the_game::logic::edit::history::{Command, History};
+use sprint_the_game::logic::input::InputAction;
+use sprint_the_game::logic::play::level::{Level, Tile};
+use sprint_the_game::logic::scene::Scene;
+
+mod common;
+
+use common::scratch_directory;
+
+// Small deterministic generator so failures can be reproduced from the seed alone
+
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self, bound: u32) -> u32 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+
+        return ((self.0 >> 33) % bound as u64) as u32;
+    }
+}
+
+fn sample_level() -> Level {
+    let mut level = Level::new(8, 5);
+
+    level.name = "Sample".to_string();
+
+    for x in 0..8 {
+        level.set((x, 0), Tile::Wall);
+        level.set((x, 4), Tile::Wall);
+    }
+
+    level.set((1, 2), Tile::Start);
+    level.set((6, 2), Tile::Goal);
+    level.set((4, 3), Tile::Spike);
+
+    return level;
+}
+
+fn random_command(level: &Level, random: &mut Random) -> Command {
+    let cell = (random.next(level.width), random.next(level.height));
+    let tile = Tile::ALL[random.next(Tile::ALL.len() as u32) as usize];
+
+    return match random.next(5) {
+        0 => {
+            let cells = (0..1 + random.next(6))
+                .map(|_| (random.next(level.width), random.next(level.height)))
+                .collect::<Vec<(u32, u32)>>();
+
+            Command::paint(level, &cells, tile)
+        }
+        1 => Command::paint(level, &[cell], Tile::Empty),
+        2 => Command::fill(level, cell, tile),
+        3 => Command::resize(level, (2 + random.next(12), 2 + random.next(8))),
+        _ => Command::rename(level, &format!("Level {}", random.next(100))),
+    };
+}
+
+#[test]
+fn full_undo_restores_the_original_level() {
+    for seed in 0..50 {
+        let original = sample_level();
+        let mut level = original.clone();
+        let mut history = History::new();
+        let mut random = Random(seed);
+
+        for _ in 0..40 {
+            let command = random_command(&level, &mut random);
+
+            history.execute(command, &mut level);
+        }
+
+        let edited = level.clone();
+
+        while history.undo(&mut level) {}
+
+        assert_eq!(level, original, "seed {}", seed);
+
+        while history.redo(&mut level) {}
+
+        assert_eq!(level, edited, "seed {}", seed);
+    }
+}
+
+#[test]
+fn fill_stops_at_different_tiles() {
+    let mut level = sample_level();
+    let mut history = History::new();
+
+    history.execute(Command::fill(&level, (3, 2), Tile::Spike), &mut level);
+
+    assert_eq!(level.get((3, 1)), Tile::Spike);
+    assert_eq!(level.get((7, 3)), Tile::Spike);
+    assert_eq!(level.get((3, 0)), Tile::Wall);
+    assert_eq!(level.get((1, 2)), Tile::Start);
+
+    history.undo(&mut level);
+
+    assert_eq!(level, sample_level());
+}
+
+#[test]
+fn new_command_discards_redo() {
+    let mut level = sample_level();
+    let mut history = History::new();
+
+    history.execute(Command::paint(&level, &[(2, 2)], Tile::Wall), &mut level);
+    history.undo(&mut level);
+
+    assert!(history.can_redo());
+
+    history.execute(Command::rename(&level, "Other"), &mut level);
+
+    assert!(!history.can_redo());
+    assert!(!history.redo(&mut level));
+}
+
+#[test]
+fn drag_stroke_is_undone_as_one_command() {
+    let mut edit = EditLogic::new();
+
+    edit.layout((1280, 720));
+    edit.edit(Level::new(10, 6), None);
+
+    let ((left, up), cell) = edit.grid;
+    let center = |x: u32, y: u32| ((left + x * cell + cell / 2) as f32, (up + y * cell + cell / 2) as f32);
+
+    edit.process_cursor(center(1, 1));
+    edit.process_action(InputAction::PrimaryClick);
+
+    for x in 2..6 {
+        edit.process_cursor(center(x, 1));
+    }
+
+    edit.process_action(InputAction::PrimaryRelease);
+
+    assert_eq!(edit.level.tiles().iter().filter(|tile| **tile != Tile::Empty).count(), 5);
+
+    edit.process_action(InputAction::Undo);
+
+    assert_eq!(edit.level, Level::new(10, 6));
+    assert!(!edit.history.can_undo());
+
+    edit.process_action(InputAction::Redo);
+
+    assert_eq!(edit.level.tiles().iter().filter(|tile| **tile != Tile::Empty).count(), 5);
+}
+
+#[test]
+fn leaving_the_window_commits_the_open_stroke() {
+    let mut edit = EditLogic::new();
+
+    edit.layout((1280, 720));
+    edit.edit(Level::new(10, 6), None);
+
+    let ((left, up), cell) = edit.grid;
+    let center = |x: u32, y: u32| ((left + x * cell + cell / 2) as f32, (up + y * cell + cell / 2) as f32);
+
+    edit.process_cursor(center(1, 1));
+    edit.process_action(InputAction::PrimaryClick);
+    edit.process_cursor(center(1, 3));
+    edit.process_cursor_left();
+
+    assert!(edit.history.can_undo());
+    assert_eq!(edit.hovered_cell, None);
+
+    // Coming back with the button still up must not keep painting
+
+    edit.process_cursor(center(4, 4));
+
+    assert_eq!(edit.level.get((4, 4)), Tile::Empty);
+
+    edit.process_action(InputAction::Undo);
+
+    assert_eq!(edit.level, Level::new(10, 6));
+}
+
+#[test]
+fn oversized_level_files_open_as_an_error() {
+    let directory = scratch_directory("edit_oversized");
+    let path = directory.join("huge.toml");
+    let row = format!("\"{}\"", ".".repeat(200));
+
+    fs::write(&path, format!("rows = [{}]\n", vec![row; 100].join(", "))).unwrap();
+
+    let mut edit = EditLogic::new();
+
+    edit.open(path);
+
+    let Some(Dialog::Error(error)) = &edit.dialog else {
+        panic!("expected an error dialog, got {:?}", edit.dialog);
+    };
+
+    assert!(error.contains("200x100 tiles"), "{}", error);
+    assert_eq!(edit.level, EditLogic::new().level);
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sprint_the_game::logic::event::{EventBus, GameEvent, Listener};
+use sprint_the_game::logic::menu::MenuLogic;
+use sprint_the_game::logic::scene::Scene;
+use sprint_the_game::logic::victory::LevelResult;
+
+mod common;
+
+use common::scratch_context;
+
+#[test]
+fn events_are_drained_in_publish_order() {
+    let mut events = EventBus::new();
+
+    events.publish(GameEvent::LevelSelected(2));
+    events.publish(GameEvent::LevelStarted(2));
+    events.publish(GameEvent::Paused(2));
+
+    assert_eq!(events.drain(), vec![GameEvent::LevelSelected(2), GameEvent::LevelStarted(2), GameEvent::Paused(2)]);
+    assert!(events.drain().is_empty());
+
+    events.publish(GameEvent::Resumed(2));
+
+    assert_eq!(events.drain(), vec![GameEvent::Resumed(2)]);
+}
+
+// Stands in for a statistics subsystem, it only sees the bus and never a scene
+
+struct DeathCounter {
+    deaths: Rc<RefCell<u32>>,
+}
+
+impl Listener for DeathCounter {
+    fn handle_event(&mut self, event: &GameEvent) {
+        if let GameEvent::RunnerDied(_) = event {
+            *self.deaths.borrow_mut() += 1;
+        }
+    }
+}
+
+#[test]
+fn listeners_receive_every_drained_event() {
+    let mut events = EventBus::new();
+    let deaths = Rc::new(RefCell::new(0));
+
+    events.subscribe(Box::new(DeathCounter { deaths: deaths.clone() }));
+
+    events.publish(GameEvent::LevelStarted(2));
+    events.publish(GameEvent::RunnerDied(2));
+    events.publish(GameEvent::RunnerDied(2));
+
+    assert_eq!(*deaths.borrow(), 0);
+    assert_eq!(events.drain().len(), 3);
+    assert_eq!(*deaths.borrow(), 2);
+}
+
+#[test]
+fn a_won_level_unlocks_the_next_one_in_the_menu() {
+    let mut menu = MenuLogic::new();
+    let mut context = scratch_context("event_bus_unlock");
+
+    menu.apply_save(&context.save);
+
+    assert!(menu.level_buttons[1].locked);
+
+    let result = LevelResult { level_id: 0, name: "First Steps".to_string(), moves: 1, time: 1.0, stars: 3 };
+
+    context.save.record_completion(0, 1, 1.0, 3);
+    menu.handle_event(&GameEvent::LevelWon(result), &context);
+
+    assert!(!menu.level_buttons[1].locked);
+    assert!(menu.level_buttons[2].locked);
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+#![cfg(feature = "render")]
+
+use winit::dpi::PhysicalPosition;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta};
+
+use sprint_the_game::bindings::Bindings;
+use sprint_the_game::logic::bindings::KeyBindings;
+use sprint_the_game::logic::input::InputAction;
+
+#[test]
+fn mouse_buttons_map_to_clicks_and_releases() {
+    let bindings = Bindings::new(&KeyBindings::new());
+
+    assert_eq!(bindings.map_mouse(ElementState::Pressed, MouseButton::Left), Some(InputAction::PrimaryClick));
+    assert_eq!(bindings.map_mouse(ElementState::Released, MouseButton::Left), Some(InputAction::PrimaryRelease));
+    assert_eq!(bindings.map_mouse(ElementState::Pressed, MouseButton::Right), Some(InputAction::SecondaryClick));
+    assert_eq!(bindings.map_mouse(ElementState::Released, MouseButton::Right), Some(InputAction::SecondaryRelease));
+    assert_eq!(bindings.map_mouse(ElementState::Pressed, MouseButton::Middle), None);
+}
+
+#[test]
+fn wheel_direction_maps_to_scrolling() {
+    let bindings = Bindings::new(&KeyBindings::new());
+
+    assert_eq!(bindings.map_wheel(MouseScrollDelta::LineDelta(0.0, 1.0)), Some(InputAction::ScrollUp));
+    assert_eq!(bindings.map_wheel(MouseScrollDelta::LineDelta(0.0, -2.0)), Some(InputAction::ScrollDown));
+    assert_eq!(bindings.map_wheel(MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, -12.0))), Some(InputAction::ScrollDown));
+    assert_eq!(bindings.map_wheel(MouseScrollDelta::LineDelta(3.0, 0.0)), None);
+}
\ No newline at end of file
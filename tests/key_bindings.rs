@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::PathBuf;
+
+use sprint_the_game::logic::bindings::{BindingsLogic, KeyBindings};
+use sprint_the_game::logic::input::{InputAction, KeyCapture};
+use sprint_the_game::logic::scene::Scene;
+
+mod common;
+
+use common::scratch_directory;
+
+fn scratch_file(name: &str, content: &str) -> PathBuf {
+    let path = scratch_directory(&format!("bindings_{}", name)).join("bindings.toml");
+
+    fs::write(&path, content).unwrap();
+
+    return path;
+}
+
+#[test]
+fn load_keeps_overrides_and_fills_in_missing_actions() {
+    let path = scratch_file("partial", "[keys]\nMoveUp = [\"KeyI\"]\nUndo = []\n");
+    let bindings = KeyBindings::load(&path).unwrap();
+    let defaults = KeyBindings::new();
+
+    assert_eq!(bindings.keys[&InputAction::MoveUp], vec!["KeyI".to_string()]);
+    assert!(bindings.keys[&InputAction::Undo].is_empty());
+    assert_eq!(bindings.keys[&InputAction::MoveDown], defaults.keys[&InputAction::MoveDown]);
+    assert_eq!(bindings.keys.len(), defaults.keys.len());
+    assert_eq!(bindings.action_for("KeyI"), Some(InputAction::MoveUp));
+    assert_eq!(bindings.action_for("ArrowUp"), None);
+}
+
+#[test]
+fn load_rejects_malformed_files() {
+    let path = scratch_file("broken", "[keys\nMoveUp = ");
+
+    assert!(KeyBindings::load(&path).is_err());
+    assert!(KeyBindings::load(&path.with_file_name("missing.toml")).is_err());
+}
+
+#[test]
+fn defaults_have_no_conflicts() {
+    assert!(KeyBindings::new().conflicts().is_empty());
+}
+
+#[test]
+fn conflicts_report_each_shared_key_once() {
+    let mut bindings = KeyBindings::new();
+
+    bindings.keys.get_mut(&InputAction::Restart).unwrap().push("KeyW".to_string());
+    bindings.keys.get_mut(&InputAction::Fill).unwrap().push("KeyW".to_string());
+    bindings.keys.get_mut(&InputAction::MoveUp).unwrap().push("KeyW".to_string());
+
+    let conflicts = bindings.conflicts();
+
+    assert_eq!(conflicts.len(), 2);
+    assert!(conflicts.iter().all(|(key, first, _)| key == "KeyW" && *first == InputAction::MoveUp));
+    assert!(conflicts.iter().any(|(_, _, second)| *second == InputAction::Restart));
+    assert!(conflicts.iter().any(|(_, _, second)| *second == InputAction::Fill));
+    assert_eq!(bindings.action_for("KeyW"), Some(InputAction::MoveUp));
+}
+
+#[test]
+fn rebinding_captures_the_next_key_instead_of_an_action() {
+    let mut logic = BindingsLogic::with_bindings(KeyBindings::new(), scratch_directory("bindings_capture").join("bindings.toml"));
+
+    assert_eq!(logic.capture(), KeyCapture::Actions);
+
+    logic.process_action(InputAction::Confirm);
+
+    assert_eq!(logic.capture(), KeyCapture::Key);
+
+    logic.process_key("F12".to_string());
+
+    assert_eq!(logic.capture(), KeyCapture::Actions);
+    assert_eq!(logic.key_bindings.action_for("F12"), Some(logic.selected_action()));
+
+    logic.process_action(InputAction::Confirm);
+    logic.process_key("Escape".to_string());
+
+    assert_eq!(logic.capture(), KeyCapture::Actions);
+    assert!(logic.message.is_none());
+}
\ No newline at end of file
@@ -0,0 +1,271 @@
+use sprint_the_game::logic::event::GameEvent;
+use sprint_the_game::logic::input::InputAction;
+use sprint_the_game::logic::play::{PlayLogic, Slide};
+use sprint_the_game::logic::play::level::{Grid, Level, Tile};
+use sprint_the_game::logic::scene::{Context, Scene};
+use sprint_the_game::logic::victory::{LevelResult, VictoryChoice, VictoryLogic};
+use sprint_the_game::save::SaveGame;
+use sprint_the_game::sprint_the_game::{Payload, State, StateStack};
+
+mod common;
+
+use common::scratch_context;
+
+fn start(id: u32, context: &mut Context) -> (PlayLogic, StateStack) {
+    let mut play = PlayLogic::new();
+    let mut stack = StateStack::new(State::Menu);
+
+    stack.replace_with(State::Play, Payload::Level(id));
+
+    for (_, payload) in stack.apply() {
+        play.enter(payload, context);
+    }
+
+    return (play, stack);
+}
+
+// Moves are applied on the next tick, like Application would run it
+
+fn perform(play: &mut PlayLogic, action: InputAction, stack: &mut StateStack, context: &mut Context) {
+    play.process_action(action);
+    play.update(1.0 / 120.0, stack, context);
+}
+
+#[test]
+fn the_runner_slides_until_a_wall_stops_it() {
+    let mut context = scratch_context("play_slide");
+    let (mut play, mut stack) = start(1, &mut context);
+
+    assert_eq!(play.runner, Some((1, 1)));
+
+    perform(&mut play, InputAction::MoveRight, &mut stack, &mut context);
+
+    assert_eq!(play.runner, Some((8, 1)));
+    assert_eq!(play.moves, 1);
+
+    perform(&mut play, InputAction::MoveRight, &mut stack, &mut context);
+
+    assert_eq!(play.moves, 1);
+}
+
+#[test]
+fn the_previous_runner_is_kept_for_one_tick() {
+    let mut context = scratch_context("play_previous");
+    let (mut play, mut stack) = start(1, &mut context);
+    let revision = play.revision();
+
+    perform(&mut play, InputAction::MoveRight, &mut stack, &mut context);
+
+    assert_eq!(play.previous_runner, Some((1, 1)));
+    assert_eq!(play.runner, Some((8, 1)));
+    assert_ne!(play.revision(), revision);
+
+    let revision = play.revision();
+
+    play.update(1.0 / 120.0, &mut stack, &mut context);
+
+    assert_eq!(play.previous_runner, Some((8, 1)));
+    assert_eq!(play.revision(), revision);
+}
+
+#[test]
+fn the_latest_slide_ages_with_every_tick() {
+    let mut context = scratch_context("play_slide_age");
+    let (mut play, mut stack) = start(2, &mut context);
+
+    perform(&mut play, InputAction::MoveRight, &mut stack, &mut context);
+
+    assert_eq!(play.slide, Some(Slide { from: (1, 1), to: (6, 1), age: 0.0, previous_age: 0.0 }));
+
+    play.update(0.25, &mut stack, &mut context);
+    play.update(0.25, &mut stack, &mut context);
+
+    assert_eq!(play.slide.map(|slide| (slide.previous_age, slide.age)), Some((0.25, 0.5)));
+
+    perform(&mut play, InputAction::Undo, &mut stack, &mut context);
+
+    assert_eq!(play.slide, None);
+}
+
+#[test]
+fn holding_rewind_scrubs_back_through_the_slide() {
+    let mut context = scratch_context("play_rewind");
+    let (mut play, mut stack) = start(1, &mut context);
+
+    perform(&mut play, InputAction::MoveRight, &mut stack, &mut context);
+
+    for _ in 0..60 {
+        play.update(1.0 / 120.0, &mut stack, &mut context);
+    }
+
+    let age = play.slide.unwrap().age;
+
+    perform(&mut play, InputAction::Rewind, &mut stack, &mut context);
+
+    assert!(play.rewinding);
+    assert_eq!(play.runner, Some((8, 1)));
+    assert!(play.slide.unwrap().age < age);
+
+    for _ in 0..60 {
+        play.update(1.0 / 120.0, &mut stack, &mut context);
+    }
+
+    assert_eq!(play.runner, Some((1, 1)));
+    assert_eq!(play.slide, None);
+    assert_eq!(play.moves, 0);
+
+    perform(&mut play, InputAction::RewindRelease, &mut stack, &mut context);
+    perform(&mut play, InputAction::MoveDown, &mut stack, &mut context);
+
+    assert!(!play.rewinding);
+    assert_eq!(play.moves, 1);
+}
+
+#[test]
+fn reaching_the_goal_completes_the_level() {
+    let mut context = scratch_context("play_goal");
+    let (mut play, mut stack) = start(0, &mut context);
+
+    perform(&mut play, InputAction::MoveRight, &mut stack, &mut context);
+
+    assert_eq!(play.moves, 1);
+
+    let entered = stack.apply();
+
+    assert_eq!(stack.states(), &[State::Play, State::Victory]);
+
+    let Some((State::Victory, Some(Payload::Result(result)))) = entered.first() else {
+        panic!("expected the victory screen, got {:?}", entered);
+    };
+
+    assert_eq!(result.level_id, 0);
+    assert_eq!(result.name, "First Steps");
+    assert_eq!(result.moves, 1);
+    assert_eq!(result.stars, 3);
+    assert!(context.save.is_completed(0));
+    assert_eq!(SaveGame::load(&context.save_path).unwrap().record(0).unwrap().stars, 3);
+}
+
+#[test]
+fn spikes_send_the_runner_back_and_undo_takes_the_move_back() {
+    let mut context = scratch_context("play_spikes");
+    let (mut play, mut stack) = start(2, &mut context);
+    let start = play.level.as_ref().unwrap().find(Tile::Start);
+
+    context.events.drain();
+
+    perform(&mut play, InputAction::MoveRight, &mut stack, &mut context);
+
+    assert_eq!(play.runner, start);
+    assert_eq!(play.moves, 1);
+    assert!(!play.is_completed());
+    assert_eq!(context.events.drain(), vec![GameEvent::RunnerDied(2)]);
+
+    perform(&mut play, InputAction::MoveDown, &mut stack, &mut context);
+
+    assert_eq!(play.runner, Some((1, 3)));
+    assert_eq!(play.moves, 2);
+
+    perform(&mut play, InputAction::Undo, &mut stack, &mut context);
+
+    assert_eq!(play.runner, start);
+    assert_eq!(play.moves, 1);
+
+    perform(&mut play, InputAction::Restart, &mut stack, &mut context);
+
+    assert_eq!(play.moves, 0);
+}
+
+#[test]
+fn more_moves_earn_fewer_stars() {
+    let level = Level::builtin(0).unwrap();
+
+    assert_eq!(level.stars_for(1), 3);
+    assert_eq!(level.stars_for(3), 2);
+    assert_eq!(level.stars_for(4), 1);
+}
+
+#[test]
+fn next_level_and_retry_reload_the_play_scene() {
+    let mut context = scratch_context("play_next");
+    let (mut play, _) = start(0, &mut context);
+
+    play.process_action(InputAction::MoveRight);
+    play.enter(Some(Payload::Retry), &mut context);
+
+    assert_eq!(play.get_current_level_id(), 0);
+    assert_eq!(play.moves, 0);
+    assert!(!play.is_completed());
+
+    play.enter(Some(Payload::NextLevel), &mut context);
+
+    assert_eq!(play.get_current_level_id(), 1);
+    assert_eq!(play.level.as_ref().map(|level| level.name.as_str()), Some("Around The Corner"));
+    assert_eq!(play.runner, play.level.as_ref().unwrap().find(Tile::Start));
+}
+
+#[test]
+fn victory_on_the_last_level_has_no_next_level() {
+    let mut victory = VictoryLogic::new();
+    let result = |level_id| LevelResult { level_id, name: String::new(), moves: 1, time: 1.0, stars: 1 };
+
+    victory.set_result(result(0));
+
+    assert_eq!(victory.panel.buttons[0].choice, VictoryChoice::NextLevel);
+
+    victory.set_result(result(Level::builtin_count() - 1));
+
+    assert!(victory.panel.buttons.iter().all(|button| button.choice != VictoryChoice::NextLevel));
+    assert_eq!(victory.panel.buttons.len(), 2);
+}
+
+#[test]
+fn hex_levels_keep_their_grid_through_a_round_trip() {
+    let level = Level::parse("name = \"Honeycomb\"\ngrid = \"hex\"\nrows = [\"S..\", \".#G\"]\n").unwrap();
+
+    assert_eq!(level.grid, Grid::Hex);
+    assert_eq!(Level::parse(&level.to_text().unwrap()).unwrap(), level);
+    assert_eq!(Level::builtin(0).unwrap().grid, Grid::Square);
+}
+
+#[test]
+fn hex_levels_slide_in_six_directions() {
+    let mut context = scratch_context("play_hex");
+    let mut play = PlayLogic::new();
+    let mut stack = StateStack::new(State::Play);
+    let mut level = Level::new(5, 5);
+
+    level.grid = Grid::Hex;
+    level.set((2, 2), Tile::Start);
+
+    play.enter(Some(Payload::Playtest(level)), &mut context);
+
+    let moves = [
+        (InputAction::MoveUp, (2, 0)),
+        (InputAction::MoveDown, (2, 4)),
+        (InputAction::MoveUpLeft, (0, 1)),
+        (InputAction::MoveUpRight, (4, 1)),
+        (InputAction::MoveLeft, (0, 3)),
+        (InputAction::MoveRight, (4, 3)),
+    ];
+
+    for (action, end) in moves {
+        perform(&mut play, action, &mut stack, &mut context);
+
+        assert_eq!(play.runner, Some(end), "{:?}", action);
+
+        perform(&mut play, InputAction::Restart, &mut stack, &mut context);
+    }
+}
+
+#[test]
+fn square_levels_ignore_the_diagonals() {
+    let mut context = scratch_context("play_diagonal");
+    let (mut play, mut stack) = start(1, &mut context);
+
+    perform(&mut play, InputAction::MoveUpLeft, &mut stack, &mut context);
+    perform(&mut play, InputAction::MoveUpRight, &mut stack, &mut context);
+
+    assert_eq!(play.runner, Some((1, 1)));
+    assert_eq!(play.moves, 0);
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use sprint_the_game::logic::edit::{Dialog, EditLogic};
+use sprint_the_game::logic::input::InputAction;
+use sprint_the_game::logic::play::PlayLogic;
+use sprint_the_game::logic::play::level::{Level, Tile};
+use sprint_the_game::logic::scene::{Context, Scene};
+use sprint_the_game::sprint_the_game::{State, StateStack};
+
+mod common;
+
+use common::scratch_context;
+
+const TICK: f32 = 1.0 / 120.0;
+
+fn corridor() -> Level {
+    let mut level = Level::new(8, 3);
+
+    level.name = "Corridor".to_string();
+    level.set((1, 1), Tile::Start);
+    level.set((6, 1), Tile::Goal);
+
+    return level;
+}
+
+// Starts a playtest of the given level from the editor, the play scene is entered like Application would
+
+fn start_playtest(level: Level, context: &mut Context) -> (EditLogic, PlayLogic, StateStack) {
+    let mut edit = EditLogic::new();
+    let mut play = PlayLogic::new();
+    let mut stack = StateStack::new(State::Edit);
+
+    edit.edit(level, None);
+    edit.process_action(InputAction::Playtest);
+    edit.update(TICK, &mut stack, context);
+
+    for (state, payload) in stack.apply() {
+        assert_eq!(state, State::Play);
+
+        play.enter(payload, context);
+    }
+
+    return (edit, play, stack);
+}
+
+#[test]
+fn winning_a_playtest_returns_to_the_editor() {
+    let mut context = scratch_context("playtest_win");
+    let (edit, mut play, mut stack) = start_playtest(corridor(), &mut context);
+
+    assert_eq!(stack.states(), &[State::Edit, State::Play]);
+    assert!(play.playtest);
+
+    play.process_action(InputAction::MoveRight);
+    play.update(TICK, &mut stack, &mut context);
+
+    assert!(stack.apply().is_empty());
+    assert_eq!(stack.states(), &[State::Edit]);
+    assert_eq!(edit.level, corridor());
+    assert!(context.save.levels.is_empty());
+    assert!(context.events.drain().is_empty());
+}
+
+#[test]
+fn leaving_a_playtest_returns_to_the_editor() {
+    let mut context = scratch_context("playtest_leave");
+    let (_, mut play, mut stack) = start_playtest(corridor(), &mut context);
+
+    play.process_action(InputAction::Back);
+    play.update(TICK, &mut stack, &mut context);
+    stack.apply();
+
+    assert_eq!(stack.states(), &[State::Edit]);
+}
+
+#[test]
+fn a_level_without_a_start_is_not_playtested() {
+    let mut context = scratch_context("playtest_no_start");
+    let mut level = corridor();
+
+    level.set((1, 1), Tile::Empty);
+
+    let mut edit = EditLogic::new();
+    let mut stack = StateStack::new(State::Edit);
+
+    edit.edit(level, None);
+    edit.process_action(InputAction::Playtest);
+    edit.update(TICK, &mut stack, &mut context);
+
+    assert!(stack.apply().is_empty());
+    assert!(matches!(edit.dialog, Some(Dialog::Error(_))));
+}
\ No newline at end of file
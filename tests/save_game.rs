@@ -0,0 +1,139 @@
+use std::fs;
+
+use sprint_the_game::logic::menu::{MenuLogic, UnlockRule};
+use sprint_the_game::logic::play::level::Level;
+use sprint_the_game::save::{SaveGame, SAVE_VERSION};
+
+mod common;
+
+use common::scratch_directory;
+
+#[test]
+fn save_round_trips_through_the_file() {
+    let path = scratch_directory("save_round_trip").join("save.toml");
+    let mut save = SaveGame::new();
+
+    save.record_completion(0, 12, 8.5, 2);
+    save.record_completion(0, 9, 11.0, 1);
+    save.record_completion(3, 4, 2.25, 3);
+
+    save.save(&path).unwrap();
+
+    let loaded = SaveGame::load(&path).unwrap();
+    let record = loaded.record(0).unwrap();
+
+    assert_eq!(loaded, save);
+    assert_eq!(record.best_moves, Some(9));
+    assert_eq!(record.best_time, Some(8.5));
+    assert_eq!(record.stars, 2);
+    assert!(loaded.is_completed(3));
+    assert!(!loaded.is_completed(1));
+    assert!(!path.with_extension("toml.tmp").exists());
+}
+
+#[test]
+fn corrupt_save_is_backed_up_and_replaced() {
+    let directory = scratch_directory("save_corrupt");
+    let path = directory.join("save.toml");
+
+    fs::write(&path, "levels = [{ id = \"zero\"").unwrap();
+
+    let save = SaveGame::load_or_default(&path);
+
+    assert_eq!(save, SaveGame::new());
+    assert!(!path.exists());
+
+    let backups = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("save.toml.corrupt-"))
+        .collect::<Vec<String>>();
+
+    assert_eq!(backups.len(), 1);
+    assert_eq!(fs::read_to_string(directory.join(&backups[0])).unwrap(), "levels = [{ id = \"zero\"");
+
+    save.save(&path).unwrap();
+
+    assert_eq!(SaveGame::load(&path).unwrap(), save);
+}
+
+#[test]
+fn newer_save_is_kept_read_only() {
+    let directory = scratch_directory("save_newer");
+    let path = directory.join("save.toml");
+    let content = format!("version = {}\nrank = \"gold\"\n\n[[levels]]\nid = 0\ncompleted = true\nstars = 3\n", SAVE_VERSION + 1);
+
+    fs::write(&path, &content).unwrap();
+
+    let mut save = SaveGame::load_or_default(&path);
+
+    assert!(save.read_only);
+    assert!(save.is_completed(0));
+
+    save.record_completion(1, 5, 3.0, 1);
+
+    assert!(save.save(&path).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    assert_eq!(fs::read_dir(&directory).unwrap().count(), 1);
+}
+
+#[test]
+fn newer_save_with_an_unknown_layout_is_not_treated_as_corrupt() {
+    let directory = scratch_directory("save_newer_layout");
+    let path = directory.join("save.toml");
+    let content = format!("version = {}\nlevels = {{ first = 1 }}\n", SAVE_VERSION + 1);
+
+    fs::write(&path, &content).unwrap();
+
+    let save = SaveGame::load_or_default(&path);
+
+    assert!(save.read_only);
+    assert!(save.levels.is_empty());
+    assert_eq!(fs::read_to_string(&path).unwrap(), content);
+}
+
+#[test]
+fn version_zero_save_is_migrated() {
+    let path = scratch_directory("save_migrate").join("save.toml");
+
+    fs::write(&path, "[[levels]]\nid = 2\ncompleted = false\nstars = 1\n\n[[levels]]\nid = 0\ncompleted = true\nstars = 2\n\n[[levels]]\nid = 2\ncompleted = true\nstars = 0\n").unwrap();
+
+    let save = SaveGame::load(&path).unwrap();
+
+    assert_eq!(save.version, SAVE_VERSION);
+    assert_eq!(save.levels.iter().map(|record| record.id).collect::<Vec<u32>>(), vec![0, 2]);
+    assert!(save.is_completed(2));
+    assert_eq!(save.record(2).unwrap().stars, 1);
+    assert!(!save.read_only);
+}
+
+#[test]
+fn star_total_rule_comes_from_the_level_file() {
+    let mut menu = MenuLogic::new();
+    let mut save = SaveGame::new();
+    let last = menu.level_buttons.len() - 1;
+
+    assert_eq!(menu.level_buttons[0].unlock, UnlockRule::Always);
+    assert_eq!(menu.level_buttons[last].unlock, UnlockRule::TotalStars(10));
+
+    for id in 0..last as u32 {
+        save.record_completion(id, 1, 1.0, 1);
+    }
+
+    menu.apply_save(&save);
+
+    assert!(menu.level_buttons[last].locked);
+
+    save.record_completion(0, 1, 1.0, 3);
+    save.record_completion(1, 1, 1.0, 3);
+    save.record_completion(2, 1, 1.0, 3);
+
+    menu.apply_save(&save);
+
+    assert_eq!(save.total_stars(), 11);
+    assert!(!menu.level_buttons[last].locked);
+
+    let level = Level::builtin(last as u32).unwrap();
+
+    assert_eq!(Level::parse(&level.to_text().unwrap()).unwrap(), level);
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use sprint_the_game::sprint_the_game::{Payload, State, StateStack};
+
+#[test]
+fn transitions_wait_for_apply_and_run_in_order() {
+    let mut stack = StateStack::new(State::Menu);
+
+    stack.push_with(State::Play, Payload::Level(3));
+    stack.push(State::Pause);
+
+    assert_eq!(stack.states(), &[State::Menu]);
+
+    let entered = stack.apply();
+
+    assert_eq!(stack.states(), &[State::Menu, State::Play, State::Pause]);
+    assert_eq!(stack.top(), State::Pause);
+    assert_eq!(entered, vec![(State::Play, Some(Payload::Level(3))), (State::Pause, None)]);
+    assert!(stack.apply().is_empty());
+}
+
+#[test]
+fn pop_and_replace_only_enter_the_new_state() {
+    let mut stack = StateStack::new(State::Menu);
+
+    stack.push(State::Play);
+    stack.push(State::Victory);
+    stack.apply();
+
+    stack.pop();
+    stack.replace_with(State::Play, Payload::Retry);
+
+    let entered = stack.apply();
+
+    assert_eq!(stack.states(), &[State::Menu, State::Play]);
+    assert_eq!(entered, vec![(State::Play, Some(Payload::Retry))]);
+
+    stack.replace(State::Edit);
+    stack.apply();
+
+    assert_eq!(stack.states(), &[State::Menu, State::Edit]);
+}
+
+#[test]
+fn visible_starts_at_the_topmost_full_screen_state() {
+    let mut stack = StateStack::new(State::Menu);
+
+    assert_eq!(stack.visible(), &[State::Menu]);
+
+    stack.push(State::Play);
+    stack.push(State::Pause);
+    stack.apply();
+
+    assert_eq!(stack.visible(), &[State::Play, State::Pause]);
+
+    stack.push(State::Settings);
+    stack.apply();
+
+    assert_eq!(stack.visible(), &[State::Settings]);
+}
+
+#[test]
+fn popping_the_last_state_quits_and_falls_back_to_the_menu() {
+    let mut stack = StateStack::new(State::Edit);
+
+    stack.pop();
+    stack.apply();
+
+    assert!(stack.should_quit());
+    assert_eq!(stack.states(), &[State::Menu]);
+}
+
+#[test]
+fn quit_keeps_the_states() {
+    let mut stack = StateStack::new(State::Menu);
+
+    stack.push(State::Play);
+    stack.quit();
+    stack.apply();
+
+    assert!(stack.should_quit());
+    assert_eq!(stack.states(), &[State::Menu, State::Play]);
+}
\ No newline at end of file